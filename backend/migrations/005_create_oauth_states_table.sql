@@ -0,0 +1,13 @@
+-- Create oauth_states table to protect the OAuth flow against login CSRF
+CREATE TABLE IF NOT EXISTS oauth_states (
+    state VARCHAR(128) PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+-- Create index on expires_at for cleanup of expired states
+CREATE INDEX IF NOT EXISTS idx_oauth_states_expires_at ON oauth_states(expires_at);
+
+-- Add comment explaining the table
+COMMENT ON TABLE oauth_states IS 'Single-use OAuth2 state values issued by the backend before redirecting to Discord';
+COMMENT ON COLUMN oauth_states.expires_at IS 'When the state stops being accepted by the callback';
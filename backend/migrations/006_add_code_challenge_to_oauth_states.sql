@@ -0,0 +1,4 @@
+-- Add PKCE code challenge to oauth_states
+ALTER TABLE oauth_states ADD COLUMN IF NOT EXISTS code_challenge VARCHAR(128);
+
+COMMENT ON COLUMN oauth_states.code_challenge IS 'PKCE S256 code challenge registered with the state (NULL for the backend callback flow)';
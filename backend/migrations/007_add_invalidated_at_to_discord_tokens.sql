@@ -0,0 +1,4 @@
+-- Track Discord tokens whose refresh was rejected by Discord
+ALTER TABLE discord_tokens ADD COLUMN IF NOT EXISTS invalidated_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN discord_tokens.invalidated_at IS 'When Discord rejected a refresh of this token (NULL while the token is usable)';
@@ -0,0 +1,17 @@
+-- Store Discord tokens encrypted at rest
+ALTER TABLE discord_tokens ADD COLUMN IF NOT EXISTS key_id VARCHAR(64);
+ALTER TABLE discord_tokens ADD COLUMN IF NOT EXISTS access_token_hash VARCHAR(64);
+
+-- The plaintext access token must no longer be indexed
+DROP INDEX IF EXISTS idx_discord_tokens_access_token;
+
+-- Create index on access_token_hash for token validation
+CREATE INDEX IF NOT EXISTS idx_discord_tokens_access_token_hash ON discord_tokens(access_token_hash);
+
+-- Create index on key_id to find rows that still need rotation
+CREATE INDEX IF NOT EXISTS idx_discord_tokens_key_id ON discord_tokens(key_id);
+
+COMMENT ON COLUMN discord_tokens.access_token IS 'Discord OAuth2 access token, AES-256-GCM encrypted with key_id';
+COMMENT ON COLUMN discord_tokens.refresh_token IS 'Discord OAuth2 refresh token, AES-256-GCM encrypted with key_id';
+COMMENT ON COLUMN discord_tokens.key_id IS 'ID of the encryption key that sealed the tokens (NULL for legacy plaintext rows awaiting rotation)';
+COMMENT ON COLUMN discord_tokens.access_token_hash IS 'HMAC-SHA256 of the access token, used for lookups';
@@ -0,0 +1,19 @@
+-- Create sessions table so issued session tokens can be revoked server-side
+CREATE TABLE IF NOT EXISTS sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ
+);
+
+-- Create index on user_id for faster lookups
+CREATE INDEX IF NOT EXISTS idx_sessions_user_id ON sessions(user_id);
+
+-- Create index on expires_at for cleanup of expired sessions
+CREATE INDEX IF NOT EXISTS idx_sessions_expires_at ON sessions(expires_at);
+
+-- Add comment explaining the table
+COMMENT ON TABLE sessions IS 'Server-side record of every issued session token';
+COMMENT ON COLUMN sessions.expires_at IS 'Matches the exp claim of the session token';
+COMMENT ON COLUMN sessions.revoked_at IS 'When the session was ended by logout (NULL while active)';
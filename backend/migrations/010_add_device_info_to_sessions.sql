@@ -0,0 +1,14 @@
+-- Record which device each session belongs to so users can review and revoke them
+ALTER TABLE sessions
+    ADD COLUMN IF NOT EXISTS device_label VARCHAR(100),
+    ADD COLUMN IF NOT EXISTS user_agent TEXT,
+    ADD COLUMN IF NOT EXISTS ip_address VARCHAR(45),
+    ADD COLUMN IF NOT EXISTS last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+-- Create index for listing a user's active sessions
+CREATE INDEX IF NOT EXISTS idx_sessions_user_id_active ON sessions(user_id) WHERE revoked_at IS NULL;
+
+COMMENT ON COLUMN sessions.device_label IS 'Human readable device name, e.g. "Firefox on Windows"';
+COMMENT ON COLUMN sessions.user_agent IS 'User-Agent header of the login request';
+COMMENT ON COLUMN sessions.ip_address IS 'Client IP address of the login request';
+COMMENT ON COLUMN sessions.last_seen_at IS 'Last time the session token was used (updated at most once a minute)';
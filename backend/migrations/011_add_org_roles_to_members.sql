@@ -0,0 +1,18 @@
+-- Create org_role enum
+CREATE TYPE org_role AS ENUM ('owner', 'admin', 'moderator', 'member');
+
+-- Add org-scoped role to members
+ALTER TABLE members ADD COLUMN IF NOT EXISTS role org_role NOT NULL DEFAULT 'member';
+
+-- Every org owner holds the owner role on a membership in their org
+INSERT INTO members (user_id, discord_org_id, role)
+SELECT owner_id, id, 'owner' FROM discord_orgs
+ON CONFLICT (user_id, discord_org_id) DO UPDATE SET role = 'owner';
+
+-- Ensure an org has at most one owner membership
+CREATE UNIQUE INDEX IF NOT EXISTS idx_members_single_owner ON members(discord_org_id) WHERE role = 'owner';
+
+-- Create composite index for role lookups
+CREATE INDEX IF NOT EXISTS idx_members_org_role ON members(discord_org_id, role);
+
+COMMENT ON COLUMN members.role IS 'Org-scoped role; permissions are derived from it in the application';
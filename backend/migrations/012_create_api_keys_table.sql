@@ -0,0 +1,25 @@
+-- Create api_keys table for org-scoped machine credentials
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    discord_org_id UUID NOT NULL REFERENCES discord_orgs(id) ON DELETE CASCADE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    name VARCHAR(100) NOT NULL,
+    key_prefix VARCHAR(16) NOT NULL,
+    key_hash VARCHAR(64) NOT NULL UNIQUE,
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    expires_at TIMESTAMPTZ,
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create index on discord_org_id for listing an org's keys
+CREATE INDEX IF NOT EXISTS idx_api_keys_discord_org_id ON api_keys(discord_org_id);
+
+-- Add comment explaining the table
+COMMENT ON TABLE api_keys IS 'Org-scoped API keys for scripts and bots; only a SHA-256 hash of each key is stored';
+COMMENT ON COLUMN api_keys.key_prefix IS 'First characters of the key, shown so users can tell keys apart';
+COMMENT ON COLUMN api_keys.key_hash IS 'Hex SHA-256 of the full key';
+COMMENT ON COLUMN api_keys.scopes IS 'Granted scopes, e.g. members:read, matches:write, leaderboard:read';
+COMMENT ON COLUMN api_keys.expires_at IS 'When the key stops working (NULL for non-expiring keys)';
+COMMENT ON COLUMN api_keys.last_used_at IS 'Last time the key authenticated a request (updated at most once a minute)';
@@ -0,0 +1,7 @@
+-- Link orgs to the Discord guild they were imported from
+ALTER TABLE discord_orgs ADD COLUMN IF NOT EXISTS guild_id VARCHAR(32);
+
+-- Create unique index so a guild can only be imported once
+CREATE UNIQUE INDEX IF NOT EXISTS idx_discord_orgs_guild_id ON discord_orgs(guild_id) WHERE guild_id IS NOT NULL;
+
+COMMENT ON COLUMN discord_orgs.guild_id IS 'Discord guild (server) snowflake this org is bound to (NULL for orgs created by hand)';
@@ -0,0 +1,40 @@
+-- Create org_invites table for shareable invite codes
+CREATE TABLE IF NOT EXISTS org_invites (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    discord_org_id UUID NOT NULL REFERENCES discord_orgs(id) ON DELETE CASCADE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    code VARCHAR(32) NOT NULL UNIQUE,
+    initial_status member_status NOT NULL DEFAULT 'spectating',
+    max_uses INTEGER CHECK (max_uses IS NULL OR max_uses > 0),
+    uses INTEGER NOT NULL DEFAULT 0,
+    expires_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create index on discord_org_id for listing an org's invites
+CREATE INDEX IF NOT EXISTS idx_org_invites_discord_org_id ON org_invites(discord_org_id);
+
+-- Create invite_redemptions table recording who joined through which code
+CREATE TABLE IF NOT EXISTS invite_redemptions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    invite_id UUID NOT NULL REFERENCES org_invites(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    member_id UUID REFERENCES members(id) ON DELETE SET NULL,
+    redeemed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- A user can only redeem a given code once
+    UNIQUE(invite_id, user_id)
+);
+
+-- Create index on invite_id for listing a code's redemptions
+CREATE INDEX IF NOT EXISTS idx_invite_redemptions_invite_id ON invite_redemptions(invite_id);
+
+-- Add comments explaining the tables
+COMMENT ON TABLE org_invites IS 'Invite codes that let users join an org without an existing member adding them';
+COMMENT ON COLUMN org_invites.initial_status IS 'Status granted to members who join with this code';
+COMMENT ON COLUMN org_invites.max_uses IS 'How many times the code can be redeemed (NULL for unlimited)';
+COMMENT ON COLUMN org_invites.uses IS 'How many times the code has been redeemed';
+COMMENT ON COLUMN org_invites.expires_at IS 'When the code stops working (NULL for non-expiring codes)';
+COMMENT ON TABLE invite_redemptions IS 'Which users joined an org through which invite code';
+COMMENT ON COLUMN invite_redemptions.member_id IS 'Membership created by the redemption (NULL once that membership is removed)';
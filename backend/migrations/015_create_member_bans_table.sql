@@ -0,0 +1,51 @@
+-- Create member_bans table recording who was banned from an org, by whom and until when
+CREATE TABLE IF NOT EXISTS member_bans (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    discord_org_id UUID NOT NULL REFERENCES discord_orgs(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    banned_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reason TEXT,
+    banned_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ,
+    lifted_at TIMESTAMPTZ,
+    lifted_by UUID REFERENCES users(id) ON DELETE SET NULL
+);
+
+-- Create unique index so a user has at most one open ban per org
+CREATE UNIQUE INDEX IF NOT EXISTS idx_member_bans_open ON member_bans(discord_org_id, user_id) WHERE lifted_at IS NULL;
+
+-- Create index on expires_at for the expiry sweeper
+CREATE INDEX IF NOT EXISTS idx_member_bans_expires_at ON member_bans(expires_at) WHERE lifted_at IS NULL AND expires_at IS NOT NULL;
+
+-- Record a permanent ban for members who were banned before this table existed
+INSERT INTO member_bans (discord_org_id, user_id, banned_at)
+SELECT discord_org_id, user_id, updated_at FROM members WHERE status = 'banned'
+ON CONFLICT DO NOTHING;
+
+-- Block memberships from being created while the user has an active ban in the org
+CREATE OR REPLACE FUNCTION reject_banned_member_insert()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM member_bans
+        WHERE discord_org_id = NEW.discord_org_id
+          AND user_id = NEW.user_id
+          AND lifted_at IS NULL
+          AND (expires_at IS NULL OR expires_at > NOW())
+    ) THEN
+        RAISE EXCEPTION 'user % is banned from org %', NEW.user_id, NEW.discord_org_id
+            USING ERRCODE = 'BAN01';
+    END IF;
+    RETURN NEW;
+END;
+$$ language 'plpgsql';
+
+-- Create trigger to run the ban check on every new membership
+CREATE TRIGGER reject_banned_member_insert BEFORE INSERT ON members
+    FOR EACH ROW EXECUTE FUNCTION reject_banned_member_insert();
+
+-- Add comments explaining the table
+COMMENT ON TABLE member_bans IS 'Org bans; the member keeps status banned while a ban is open';
+COMMENT ON COLUMN member_bans.expires_at IS 'When the ban ends on its own (NULL for permanent bans)';
+COMMENT ON COLUMN member_bans.lifted_at IS 'When the ban was lifted, by an unban or by expiring (NULL while open)';
+COMMENT ON COLUMN member_bans.lifted_by IS 'Who unbanned the member (NULL for expired bans)';
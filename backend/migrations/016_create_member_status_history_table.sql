@@ -0,0 +1,18 @@
+-- Create member_status_history table recording every member status change
+CREATE TABLE IF NOT EXISTS member_status_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    member_id UUID NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    from_status member_status,
+    to_status member_status NOT NULL,
+    changed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reason TEXT,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create index for reading a member's history in order
+CREATE INDEX IF NOT EXISTS idx_member_status_history_member_id ON member_status_history(member_id, changed_at);
+
+-- Add comments explaining the table
+COMMENT ON TABLE member_status_history IS 'Audit trail of member status changes; starts from when this table was added';
+COMMENT ON COLUMN member_status_history.from_status IS 'Status before the change (NULL when the membership was created)';
+COMMENT ON COLUMN member_status_history.changed_by IS 'User who made the change (NULL for automatic changes such as ban expiry)';
@@ -0,0 +1,34 @@
+-- Let orgs require admin approval before users can join
+ALTER TABLE discord_orgs ADD COLUMN IF NOT EXISTS requires_approval BOOLEAN NOT NULL DEFAULT FALSE;
+
+COMMENT ON COLUMN discord_orgs.requires_approval IS 'Whether joining creates a join request that an admin has to approve';
+
+-- Create join_request_status enum
+CREATE TYPE join_request_status AS ENUM ('pending', 'approved', 'rejected', 'withdrawn');
+
+-- Create join_requests table for users applying to orgs that require approval
+CREATE TABLE IF NOT EXISTS join_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    discord_org_id UUID NOT NULL REFERENCES discord_orgs(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    message TEXT,
+    status join_request_status NOT NULL DEFAULT 'pending',
+    decided_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    decision_reason TEXT,
+    member_id UUID REFERENCES members(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    decided_at TIMESTAMPTZ
+);
+
+-- Create unique index so a user has at most one pending request per org
+CREATE UNIQUE INDEX IF NOT EXISTS idx_join_requests_pending ON join_requests(discord_org_id, user_id) WHERE status = 'pending';
+
+-- Create index on user_id for listing a user's requests
+CREATE INDEX IF NOT EXISTS idx_join_requests_user_id ON join_requests(user_id);
+
+-- Add comments explaining the table
+COMMENT ON TABLE join_requests IS 'Requests to join orgs that require approval';
+COMMENT ON COLUMN join_requests.message IS 'Optional message from the applicant to the org admins';
+COMMENT ON COLUMN join_requests.decided_by IS 'Admin who approved or rejected the request (NULL while pending or once withdrawn)';
+COMMENT ON COLUMN join_requests.decided_at IS 'When the request was approved, rejected or withdrawn (NULL while pending)';
+COMMENT ON COLUMN join_requests.member_id IS 'Membership created by approving the request';
@@ -0,0 +1,10 @@
+-- Soft-delete users and orgs; rows are hard-deleted by the purge task once the retention window passes
+ALTER TABLE users ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE discord_orgs ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+-- Create partial indexes for the purge task
+CREATE INDEX IF NOT EXISTS idx_users_deleted_at ON users(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_discord_orgs_deleted_at ON discord_orgs(deleted_at) WHERE deleted_at IS NOT NULL;
+
+COMMENT ON COLUMN users.deleted_at IS 'When the user was deleted (NULL for live users); restorable until purged';
+COMMENT ON COLUMN discord_orgs.deleted_at IS 'When the org was deleted, directly or with its owner (NULL for live orgs); restorable until purged';
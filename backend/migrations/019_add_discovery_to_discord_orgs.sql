@@ -0,0 +1,17 @@
+-- Let orgs opt into the public directory and tag themselves for browsing
+ALTER TABLE discord_orgs ADD COLUMN IF NOT EXISTS listed BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE discord_orgs ADD COLUMN IF NOT EXISTS tags TEXT[] NOT NULL DEFAULT '{}';
+
+-- Full-text search document over the org's name and description
+ALTER TABLE discord_orgs ADD COLUMN IF NOT EXISTS search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', name), 'A') ||
+    setweight(to_tsvector('english', coalesce(description, '')), 'B')
+) STORED;
+
+-- Create GIN indexes for text search and tag filters
+CREATE INDEX IF NOT EXISTS idx_discord_orgs_search_vector ON discord_orgs USING GIN(search_vector);
+CREATE INDEX IF NOT EXISTS idx_discord_orgs_tags ON discord_orgs USING GIN(tags);
+
+COMMENT ON COLUMN discord_orgs.listed IS 'Whether the org shows up in the public directory';
+COMMENT ON COLUMN discord_orgs.tags IS 'Lowercase tags or categories used to browse the directory';
+COMMENT ON COLUMN discord_orgs.search_vector IS 'Full-text search document built from name (weight A) and description (weight B)';
@@ -0,0 +1,28 @@
+-- Create ownership_transfer_status enum
+CREATE TYPE ownership_transfer_status AS ENUM ('pending', 'accepted', 'declined', 'cancelled');
+
+-- Create ownership_transfers table recording every org ownership nomination and its outcome
+CREATE TABLE IF NOT EXISTS ownership_transfers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    discord_org_id UUID NOT NULL REFERENCES discord_orgs(id) ON DELETE CASCADE,
+    from_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    to_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    initiated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    status ownership_transfer_status NOT NULL DEFAULT 'pending',
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ
+);
+
+-- Create unique index so an org has at most one pending transfer
+CREATE UNIQUE INDEX IF NOT EXISTS idx_ownership_transfers_pending ON ownership_transfers(discord_org_id) WHERE status = 'pending';
+
+-- Create index on to_user_id for listing a user's incoming nominations
+CREATE INDEX IF NOT EXISTS idx_ownership_transfers_to_user_id ON ownership_transfers(to_user_id);
+
+-- Add comments explaining the table
+COMMENT ON TABLE ownership_transfers IS 'Org ownership nominations; ownership only moves once the nominee accepts';
+COMMENT ON COLUMN ownership_transfers.from_user_id IS 'Owner at the time of the nomination';
+COMMENT ON COLUMN ownership_transfers.initiated_by IS 'Who nominated the new owner (the owner, or a global admin on their behalf)';
+COMMENT ON COLUMN ownership_transfers.expires_at IS 'A pending transfer can no longer be accepted after this time';
+COMMENT ON COLUMN ownership_transfers.resolved_at IS 'When the transfer was accepted, declined or cancelled (NULL while pending)';
@@ -0,0 +1,42 @@
+-- Create matches table recording every game played within an org
+CREATE TABLE IF NOT EXISTS matches (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    discord_org_id UUID NOT NULL REFERENCES discord_orgs(id) ON DELETE CASCADE,
+    game_type VARCHAR(64) NOT NULL,
+    reported_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    started_at TIMESTAMPTZ NOT NULL,
+    ended_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CHECK (ended_at >= started_at)
+);
+
+-- Create match_participants table holding each player's result in a match
+CREATE TABLE IF NOT EXISTS match_participants (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    match_id UUID NOT NULL REFERENCES matches(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    placement INTEGER NOT NULL CHECK (placement >= 1),
+    score DOUBLE PRECISION,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Ensure a user only appears once per match
+    UNIQUE(match_id, user_id)
+);
+
+-- Create composite index for listing an org's matches by game type
+CREATE INDEX IF NOT EXISTS idx_matches_org_game_type ON matches(discord_org_id, game_type, ended_at DESC);
+
+-- Create index on user_id for computing a user's stats
+CREATE INDEX IF NOT EXISTS idx_match_participants_user_id ON match_participants(user_id);
+
+-- Create trigger to automatically update updated_at on matches table
+CREATE TRIGGER update_matches_updated_at BEFORE UPDATE ON matches
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Add comments explaining the tables
+COMMENT ON COLUMN matches.game_type IS 'Free-form game identifier chosen by the org, e.g. "chess" or "mario-kart"';
+COMMENT ON COLUMN matches.reported_by IS 'User who recorded the match (NULL when recorded with an API key)';
+COMMENT ON COLUMN match_participants.placement IS 'Finishing position starting at 1; tied players share a placement';
+COMMENT ON COLUMN match_participants.score IS 'Optional game-specific score';
@@ -0,0 +1,38 @@
+-- Create match_status enum
+CREATE TYPE match_status AS ENUM ('pending', 'disputed', 'finalized', 'voided');
+
+-- Create match_confirmation enum for each participant's answer to a reported result
+CREATE TYPE match_confirmation AS ENUM ('pending', 'confirmed', 'disputed');
+
+-- Matches recorded before results needed confirmation count as finalized
+ALTER TABLE matches
+    ADD COLUMN status match_status NOT NULL DEFAULT 'finalized',
+    ADD COLUMN confirm_deadline TIMESTAMPTZ,
+    ADD COLUMN finalized_at TIMESTAMPTZ,
+    ADD COLUMN resolved_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN resolution_note TEXT;
+
+UPDATE matches SET confirm_deadline = created_at, finalized_at = created_at;
+
+ALTER TABLE matches
+    ALTER COLUMN status SET DEFAULT 'pending',
+    ALTER COLUMN confirm_deadline SET NOT NULL;
+
+ALTER TABLE match_participants
+    ADD COLUMN confirmation match_confirmation NOT NULL DEFAULT 'confirmed',
+    ADD COLUMN dispute_reason TEXT,
+    ADD COLUMN responded_at TIMESTAMPTZ;
+
+ALTER TABLE match_participants ALTER COLUMN confirmation SET DEFAULT 'pending';
+
+-- Create partial index for the auto-finalization sweep
+CREATE INDEX IF NOT EXISTS idx_matches_pending_deadline ON matches(confirm_deadline) WHERE status = 'pending';
+
+-- Create partial index for each org's dispute queue
+CREATE INDEX IF NOT EXISTS idx_matches_org_disputed ON matches(discord_org_id) WHERE status = 'disputed';
+
+-- Add comments explaining the columns
+COMMENT ON COLUMN matches.status IS 'Only finalized matches count towards stats and ratings';
+COMMENT ON COLUMN matches.confirm_deadline IS 'A pending match nobody disputed is finalized after this time';
+COMMENT ON COLUMN matches.resolved_by IS 'Org admin who finalized or voided a disputed match';
+COMMENT ON COLUMN match_participants.confirmation IS 'Whether the participant confirmed or disputed the reported result';
@@ -0,0 +1,49 @@
+-- Create ratings table holding each player's current Glicko-2 rating per org and game type
+CREATE TABLE IF NOT EXISTS ratings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    discord_org_id UUID NOT NULL REFERENCES discord_orgs(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    game_type VARCHAR(64) NOT NULL,
+    rating DOUBLE PRECISION NOT NULL DEFAULT 1500,
+    deviation DOUBLE PRECISION NOT NULL DEFAULT 350,
+    volatility DOUBLE PRECISION NOT NULL DEFAULT 0.06,
+    games_played INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Ensure a player has one rating per org and game type
+    UNIQUE(discord_org_id, game_type, user_id)
+);
+
+-- Create rating_history table recording how each rated match changed a player's rating
+CREATE TABLE IF NOT EXISTS rating_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    match_id UUID NOT NULL REFERENCES matches(id) ON DELETE CASCADE,
+    discord_org_id UUID NOT NULL REFERENCES discord_orgs(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    game_type VARCHAR(64) NOT NULL,
+    rating_before DOUBLE PRECISION NOT NULL,
+    deviation_before DOUBLE PRECISION NOT NULL,
+    volatility_before DOUBLE PRECISION NOT NULL,
+    rating_after DOUBLE PRECISION NOT NULL,
+    deviation_after DOUBLE PRECISION NOT NULL,
+    volatility_after DOUBLE PRECISION NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Ensure a match changes each player's rating once
+    UNIQUE(match_id, user_id)
+);
+
+-- Create composite index for the leaderboard
+CREATE INDEX IF NOT EXISTS idx_ratings_leaderboard ON ratings(discord_org_id, game_type, rating DESC);
+
+-- Create composite index for charting a player's progress
+CREATE INDEX IF NOT EXISTS idx_rating_history_user ON rating_history(discord_org_id, game_type, user_id, created_at);
+
+-- Create trigger to automatically update updated_at on ratings table
+CREATE TRIGGER update_ratings_updated_at BEFORE UPDATE ON ratings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Add comments explaining the tables
+COMMENT ON TABLE ratings IS 'Current Glicko-2 ratings, updated in the same transaction that finalizes a match';
+COMMENT ON TABLE rating_history IS 'Rating change per player per finalized match; replayed in match order to recompute ratings';
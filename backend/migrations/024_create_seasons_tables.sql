@@ -0,0 +1,56 @@
+-- Create seasons table splitting an org's competitive play into dated seasons
+CREATE TABLE IF NOT EXISTS seasons (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    discord_org_id UUID NOT NULL REFERENCES discord_orgs(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    starts_at TIMESTAMPTZ NOT NULL,
+    ends_at TIMESTAMPTZ NOT NULL,
+    soft_reset DOUBLE PRECISION NOT NULL DEFAULT 0.5,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    archived_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Ensure a season covers a real period
+    CHECK (ends_at > starts_at),
+
+    -- Ensure the reset pulls ratings between 0% and 100% of the way back to the default
+    CHECK (soft_reset >= 0 AND soft_reset <= 1)
+);
+
+-- Create season_standings table freezing each season's final leaderboards
+CREATE TABLE IF NOT EXISTS season_standings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    season_id UUID NOT NULL REFERENCES seasons(id) ON DELETE CASCADE,
+    game_type VARCHAR(64) NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    rank INTEGER NOT NULL CHECK (rank >= 1),
+    rating DOUBLE PRECISION NOT NULL,
+    deviation DOUBLE PRECISION NOT NULL,
+    volatility DOUBLE PRECISION NOT NULL,
+    games_played INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Ensure a player has one final standing per season and game type
+    UNIQUE(season_id, game_type, user_id)
+);
+
+-- Create index for listing an org's seasons
+CREATE INDEX IF NOT EXISTS idx_seasons_org ON seasons(discord_org_id, starts_at DESC);
+
+-- Create partial index for finding seasons due to roll over
+CREATE INDEX IF NOT EXISTS idx_seasons_rollover ON seasons(ends_at) WHERE archived_at IS NULL;
+
+-- Create composite index for archived leaderboards
+CREATE INDEX IF NOT EXISTS idx_season_standings_rank ON season_standings(season_id, game_type, rank);
+
+-- Create trigger to automatically update updated_at on seasons table
+CREATE TRIGGER update_seasons_updated_at BEFORE UPDATE ON seasons
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Add comments explaining the tables and columns
+COMMENT ON TABLE seasons IS 'Dated competitive seasons; when one ends its standings are archived and ratings soft reset';
+COMMENT ON COLUMN seasons.soft_reset IS 'Share of the way ratings are pulled back to the default at rollover (0 keeps them, 1 resets fully)';
+COMMENT ON COLUMN seasons.archived_at IS 'When the season rolled over; NULL until then';
+COMMENT ON TABLE season_standings IS 'Final leaderboard of a season per game type, frozen at rollover';
+COMMENT ON COLUMN season_standings.games_played IS 'Finalized matches the player played during the season';
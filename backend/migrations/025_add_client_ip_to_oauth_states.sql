@@ -0,0 +1,13 @@
+-- Record which client asked for each OAuth state, so unauthenticated issuance can be capped
+ALTER TABLE oauth_states ADD COLUMN IF NOT EXISTS client_ip VARCHAR(45);
+
+-- Drop states left over from the removed backend callback flow; every state now needs a PKCE
+-- challenge
+DELETE FROM oauth_states WHERE code_challenge IS NULL;
+ALTER TABLE oauth_states ALTER COLUMN code_challenge SET NOT NULL;
+
+-- Create index for counting a client's outstanding states
+CREATE INDEX IF NOT EXISTS idx_oauth_states_client_ip ON oauth_states(client_ip, expires_at);
+
+COMMENT ON COLUMN oauth_states.client_ip IS 'IP address the state was issued to';
+COMMENT ON COLUMN oauth_states.code_challenge IS 'PKCE S256 code challenge registered with the state; the exchange must present its verifier';
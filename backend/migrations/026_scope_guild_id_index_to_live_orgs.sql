@@ -0,0 +1,3 @@
+-- Only live orgs hold on to their guild, so a guild whose org was deleted can be imported again
+DROP INDEX IF EXISTS idx_discord_orgs_guild_id;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_discord_orgs_guild_id ON discord_orgs(guild_id) WHERE guild_id IS NOT NULL AND deleted_at IS NULL;
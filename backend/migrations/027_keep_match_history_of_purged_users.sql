@@ -0,0 +1,5 @@
+-- Purged users who played matches are kept as anonymous tombstones, so other players' match
+-- history and ratings don't change when they go
+ALTER TABLE users ADD COLUMN IF NOT EXISTS purged_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN users.purged_at IS 'When the purge task anonymized the deleted user (NULL unless kept as a tombstone for match history)';
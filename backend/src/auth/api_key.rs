@@ -0,0 +1,64 @@
+//! Org-scoped API keys
+//!
+//! Keys are random strings with a recognisable prefix so the auth layer can tell
+//! them apart from session tokens. Only a SHA-256 hash is stored; the plaintext
+//! key is shown once when it is created.
+
+use rand::{Rng, distributions::Alphanumeric};
+use sha2::{Digest, Sha256};
+
+/// Every API key starts with this prefix
+pub const API_KEY_PREFIX: &str = "dohk_";
+
+const SECRET_LENGTH: usize = 40;
+const DISPLAY_PREFIX_LENGTH: usize = 12;
+
+/// A freshly generated key: the plaintext to hand out, plus what gets stored
+pub struct GeneratedApiKey {
+    pub key: String,
+    pub key_prefix: String,
+    pub key_hash: String,
+}
+
+/// Generate a new random API key
+pub fn generate_api_key() -> GeneratedApiKey {
+    let secret: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(SECRET_LENGTH)
+        .map(char::from)
+        .collect();
+    let key = format!("{}{}", API_KEY_PREFIX, secret);
+
+    GeneratedApiKey {
+        key_prefix: key[..DISPLAY_PREFIX_LENGTH].to_string(),
+        key_hash: hash_api_key(&key),
+        key,
+    }
+}
+
+/// Hex SHA-256 of a key, as stored in `api_keys.key_hash`
+pub fn hash_api_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Whether a bearer token looks like an API key rather than a session token
+pub fn is_api_key(token: &str) -> bool {
+    token.starts_with(API_KEY_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_key() {
+        let generated = generate_api_key();
+        assert!(is_api_key(&generated.key));
+        assert!(generated.key.starts_with(&generated.key_prefix));
+        assert_eq!(generated.key_hash, hash_api_key(&generated.key));
+        assert_ne!(generated.key, generate_api_key().key);
+    }
+}
@@ -0,0 +1,210 @@
+//! Axum extractors that resolve the caller from a bearer session token or API key
+//!
+//! [`AuthUser`] only accepts session tokens. [`Caller`] additionally accepts org
+//! API keys and is used on the endpoints that scripts and bots may call.
+
+use axum::{
+    Json, async_trait,
+    extract::FromRequestParts,
+    http::{StatusCode, header::AUTHORIZATION, request::Parts},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use shared::models::ApiKeyScope;
+use uuid::Uuid;
+
+use crate::auth::api_key::{hash_api_key, is_api_key};
+use crate::auth::session::verify_session_token;
+use crate::config::Config;
+use crate::database::queries;
+use crate::handlers::users::ApiResponse;
+
+/// The authenticated caller of a request
+///
+/// Add this as a handler argument to require a valid `Authorization: Bearer <session_token>`
+/// header. Requests without one are rejected with 401 before the handler runs.
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub user_id: Uuid,
+    pub session_id: Uuid,
+    pub is_admin: bool,
+}
+
+impl AuthUser {
+    /// Whether the caller may act on behalf of the given user (self or admin)
+    pub fn can_act_for(&self, user_id: Uuid) -> bool {
+        self.is_admin || self.user_id == user_id
+    }
+}
+
+/// An authenticated org API key
+#[derive(Debug, Clone)]
+pub struct ApiKeyCaller {
+    pub org_id: Uuid,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+impl ApiKeyCaller {
+    /// Whether the key belongs to the org and carries the scope
+    pub fn allows(&self, org_id: Uuid, scope: ApiKeyScope) -> bool {
+        self.org_id == org_id && self.scopes.contains(&scope)
+    }
+}
+
+/// The authenticated caller of a request: a user session or an org API key
+#[derive(Debug, Clone)]
+pub enum Caller {
+    User(AuthUser),
+    ApiKey(ApiKeyCaller),
+}
+
+/// Rejection returned when a request cannot be authenticated
+#[derive(Debug)]
+pub enum AuthError {
+    MissingToken,
+    InvalidToken(String),
+    SessionRevoked,
+    UnknownUser,
+    Database(String),
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            AuthError::MissingToken => (
+                StatusCode::UNAUTHORIZED,
+                "Missing bearer session token or API key".to_string(),
+            ),
+            AuthError::InvalidToken(e) => (StatusCode::UNAUTHORIZED, e),
+            AuthError::SessionRevoked => (
+                StatusCode::UNAUTHORIZED,
+                "Session has been revoked".to_string(),
+            ),
+            AuthError::UnknownUser => (
+                StatusCode::UNAUTHORIZED,
+                "Session user no longer exists".to_string(),
+            ),
+            AuthError::Database(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", e),
+            ),
+        };
+
+        (status, Json(ApiResponse::<()>::error(message))).into_response()
+    }
+}
+
+/// Extract the token from an `Authorization: Bearer <token>` header
+fn bearer_token(parts: &Parts) -> Option<&str> {
+    parts
+        .headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim())
+        .filter(|token| !token.is_empty())
+}
+
+async fn authenticate_session(token: &str, state: &crate::AppState) -> Result<AuthUser, AuthError> {
+    let claims = verify_session_token(token).map_err(AuthError::InvalidToken)?;
+
+    // A validly signed token is only honoured while its session row is active
+    let session = queries::get_session_by_id(&state.db_pool, claims.sid)
+        .await
+        .map_err(|e| AuthError::Database(e.to_string()))?;
+    match session {
+        Some(session) if session.user_id == claims.sub && session.revoked_at.is_none() => {}
+        _ => return Err(AuthError::SessionRevoked),
+    }
+
+    queries::touch_session(&state.db_pool, claims.sid)
+        .await
+        .map_err(|e| AuthError::Database(e.to_string()))?;
+
+    let user = queries::get_user_by_id(&state.db_pool, claims.sub)
+        .await
+        .map_err(|e| AuthError::Database(e.to_string()))?
+        .ok_or(AuthError::UnknownUser)?;
+
+    let is_admin = Config::admin_discord_ids().contains(&user.discord_id);
+
+    Ok(AuthUser {
+        user_id: user.id,
+        session_id: claims.sid,
+        is_admin,
+    })
+}
+
+async fn authenticate_api_key(
+    key: &str,
+    state: &crate::AppState,
+) -> Result<ApiKeyCaller, AuthError> {
+    let api_key = queries::get_api_key_by_hash(&state.db_pool, &hash_api_key(key))
+        .await
+        .map_err(|e| AuthError::Database(e.to_string()))?
+        .ok_or_else(|| AuthError::InvalidToken("Invalid API key".to_string()))?;
+
+    if api_key.revoked_at.is_some() {
+        return Err(AuthError::InvalidToken(
+            "API key has been revoked".to_string(),
+        ));
+    }
+    if api_key
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= Utc::now())
+    {
+        return Err(AuthError::InvalidToken("API key has expired".to_string()));
+    }
+
+    queries::touch_api_key(&state.db_pool, api_key.id)
+        .await
+        .map_err(|e| AuthError::Database(e.to_string()))?;
+
+    Ok(ApiKeyCaller {
+        org_id: api_key.discord_org_id,
+        // Scopes are validated on creation; skip anything unknown from older releases
+        scopes: api_key
+            .scopes
+            .iter()
+            .filter_map(|scope| ApiKeyScope::try_from(scope.as_str()).ok())
+            .collect(),
+    })
+}
+
+#[async_trait]
+impl FromRequestParts<crate::AppState> for AuthUser {
+    type Rejection = AuthError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &crate::AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = bearer_token(parts).ok_or(AuthError::MissingToken)?;
+
+        if is_api_key(token) {
+            return Err(AuthError::InvalidToken(
+                "API keys can't be used on this endpoint".to_string(),
+            ));
+        }
+
+        authenticate_session(token, state).await
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<crate::AppState> for Caller {
+    type Rejection = AuthError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &crate::AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = bearer_token(parts).ok_or(AuthError::MissingToken)?;
+
+        if is_api_key(token) {
+            authenticate_api_key(token, state).await.map(Caller::ApiKey)
+        } else {
+            authenticate_session(token, state).await.map(Caller::User)
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Authentication module for session tokens, API keys, OAuth state/PKCE, the request
+//! extractors and org-scoped permission checks
+
+pub mod api_key;
+pub mod extractor;
+pub mod oauth_state;
+pub mod permissions;
+pub mod pkce;
+pub mod session;
+
+pub use extractor::{AuthUser, Caller};
@@ -0,0 +1,161 @@
+//! OAuth `state` parameter handling
+//!
+//! Before sending a user to Discord the frontend asks the backend for a random
+//! state value. The backend stores it with a short expiry and the exchange must
+//! present it again; missing, reused or expired states are rejected. The frontend
+//! additionally keeps the state in `sessionStorage` and checks that the callback
+//! returns the same value, binding the flow to the browser that started it.
+//!
+//! Every state is registered with a PKCE code challenge, and the exchange must
+//! present the matching code verifier, which only the initiating browser knows.
+//! Issuing states needs no login, so each client IP can only hold a few unused
+//! states at a time, on top of a cap across all clients.
+//!
+//! Discord apps registered with the backend's redirect URI start the login at
+//! `/auth/discord/login` instead. The backend then generates the verifier itself
+//! and keeps it in an HttpOnly cookie, so the callback can only be completed by the
+//! browser that started the login.
+
+use axum::http::{HeaderMap, header::COOKIE};
+use chrono::{DateTime, Duration, Utc};
+use rand::{Rng, distributions::Alphanumeric};
+use serde::Serialize;
+
+use crate::auth::pkce;
+use crate::config::Config;
+use crate::database::{DatabasePool, queries};
+
+const STATE_LENGTH: usize = 43;
+const VERIFIER_COOKIE: &str = "discord_oauth_verifier";
+
+/// A freshly issued OAuth state value
+#[derive(Debug, Clone, Serialize)]
+pub struct OAuthState {
+    pub state: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Why a state couldn't be issued
+#[derive(Debug)]
+pub enum IssueStateError {
+    /// The client, or all clients together, already hold too many unused states
+    TooManyPending,
+    Failed(String),
+}
+
+/// Generate and persist a new single-use state value bound to a PKCE challenge
+pub async fn issue_oauth_state(
+    pool: &DatabasePool,
+    code_challenge: &str,
+    client_ip: &str,
+) -> Result<OAuthState, IssueStateError> {
+    // Opportunistically drop states that were never used
+    queries::cleanup_expired_oauth_states(pool)
+        .await
+        .map_err(|e| IssueStateError::Failed(format!("Failed to clean up OAuth states: {}", e)))?;
+
+    let state: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(STATE_LENGTH)
+        .map(char::from)
+        .collect();
+    let expires_at = Utc::now() + Duration::seconds(Config::oauth_state_ttl_seconds());
+
+    let oauth_state = queries::create_oauth_state(
+        pool,
+        &state,
+        code_challenge,
+        client_ip,
+        expires_at,
+        Config::oauth_state_max_per_client(),
+        Config::oauth_state_max_pending(),
+    )
+    .await
+    .map_err(|e| IssueStateError::Failed(format!("Failed to save OAuth state: {}", e)))?
+    .ok_or(IssueStateError::TooManyPending)?;
+
+    Ok(OAuthState {
+        state: oauth_state.state,
+        expires_at: oauth_state.expires_at,
+    })
+}
+
+/// Consume a state value returned by Discord, rejecting missing, reused or expired ones
+/// and checking `code_verifier` against the challenge registered with it
+pub async fn verify_oauth_state(
+    pool: &DatabasePool,
+    state: Option<&str>,
+    code_verifier: &str,
+) -> Result<(), String> {
+    let state = state
+        .filter(|s| !s.is_empty())
+        .ok_or("Missing OAuth state")?;
+
+    let oauth_state = match queries::consume_oauth_state(pool, state).await {
+        Ok(Some(oauth_state)) if oauth_state.expires_at > Utc::now() => oauth_state,
+        Ok(Some(_)) => return Err("OAuth state has expired".to_string()),
+        Ok(None) => return Err("Unknown or already used OAuth state".to_string()),
+        Err(e) => return Err(format!("Failed to verify OAuth state: {}", e)),
+    };
+
+    pkce::verify_code_verifier(code_verifier, &oauth_state.code_challenge)
+}
+
+/// `Set-Cookie` value holding the verifier of a login started by the backend
+pub fn verifier_cookie(code_verifier: &str) -> String {
+    let secure = if Config::backend_url().starts_with("https://") {
+        "; Secure"
+    } else {
+        ""
+    };
+
+    format!(
+        "{}={}; Path=/auth/discord; Max-Age={}; HttpOnly; SameSite=Lax{}",
+        VERIFIER_COOKIE,
+        code_verifier,
+        Config::oauth_state_ttl_seconds(),
+        secure
+    )
+}
+
+/// `Set-Cookie` value removing the verifier cookie once the callback has used it
+pub fn clear_verifier_cookie() -> String {
+    format!(
+        "{}=; Path=/auth/discord; Max-Age=0; HttpOnly; SameSite=Lax",
+        VERIFIER_COOKIE
+    )
+}
+
+/// Read the verifier cookie set by `/auth/discord/login` from a request
+pub fn verifier_from_cookies(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| *name == VERIFIER_COOKIE)
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_verifier_from_cookies() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(verifier_from_cookies(&headers), None);
+
+        headers.insert(
+            COOKIE,
+            HeaderValue::from_static("theme=dark; discord_oauth_verifier=abc123"),
+        );
+        assert_eq!(verifier_from_cookies(&headers).as_deref(), Some("abc123"));
+
+        headers.insert(COOKIE, HeaderValue::from_static("discord_oauth_verifier="));
+        assert_eq!(verifier_from_cookies(&headers), None);
+    }
+}
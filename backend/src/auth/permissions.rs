@@ -0,0 +1,177 @@
+//! Org-scoped permission checks
+//!
+//! Every org or member mutation resolves the caller's access through
+//! [`require_org_permission`], and through [`require_member_permission`] when it
+//! acts on another member. A caller's role is `Owner` if they own the org and
+//! their membership role otherwise; global admins (`ADMIN_DISCORD_IDS`) are
+//! granted every permission in every org.
+//!
+//! Endpoints that also accept API keys use [`require_caller_access`], which maps
+//! the permission a user would need to the scope a key must carry.
+
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use shared::database::DbMember;
+use shared::models::{ApiKeyScope, MemberStatus, OrgRole, Permissions};
+use uuid::Uuid;
+
+use crate::auth::{AuthUser, Caller};
+use crate::database::{DatabasePool, queries};
+use crate::handlers::users::ApiResponse;
+
+/// What the caller may do within one org
+#[derive(Debug, Clone, Serialize)]
+pub struct OrgAccess {
+    pub org_id: Uuid,
+    pub role: Option<OrgRole>,
+    pub permissions: Permissions,
+    pub is_global_admin: bool,
+}
+
+impl OrgAccess {
+    pub fn has(&self, required: Permissions) -> bool {
+        self.permissions.contains(required)
+    }
+
+    /// Whether the caller may act on a member holding `target` role
+    pub fn outranks(&self, target: OrgRole) -> bool {
+        self.is_global_admin || self.role.is_some_and(|role| role.outranks(target))
+    }
+}
+
+/// Rejection returned when an org permission check fails
+#[derive(Debug)]
+pub enum PermissionError {
+    OrgNotFound,
+    MemberNotFound,
+    Forbidden(String),
+    Database(String),
+}
+
+impl IntoResponse for PermissionError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            PermissionError::OrgNotFound => {
+                (StatusCode::NOT_FOUND, "Organization not found".to_string())
+            }
+            PermissionError::MemberNotFound => {
+                (StatusCode::NOT_FOUND, "Member not found".to_string())
+            }
+            PermissionError::Forbidden(e) => (StatusCode::FORBIDDEN, e),
+            PermissionError::Database(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Database error: {}", e),
+            ),
+        };
+
+        (status, Json(ApiResponse::<()>::error(message))).into_response()
+    }
+}
+
+/// Resolve the caller's role and permissions in an org
+pub async fn org_access(
+    pool: &DatabasePool,
+    auth: &AuthUser,
+    org_id: Uuid,
+) -> Result<OrgAccess, PermissionError> {
+    let org = queries::get_discord_org_by_id(pool, org_id)
+        .await
+        .map_err(|e| PermissionError::Database(e.to_string()))?
+        .ok_or(PermissionError::OrgNotFound)?;
+
+    let role = if org.owner_id == auth.user_id {
+        Some(OrgRole::Owner)
+    } else {
+        let member = queries::get_member_by_user_and_org(pool, auth.user_id, org_id)
+            .await
+            .map_err(|e| PermissionError::Database(e.to_string()))?;
+
+        // Banned members keep their row but lose every permission
+        member
+            .filter(|member| member.status != String::from(MemberStatus::Banned))
+            .and_then(|member| OrgRole::try_from(member.role).ok())
+    };
+
+    let permissions = if auth.is_admin {
+        Permissions::all()
+    } else {
+        role.map(OrgRole::permissions)
+            .unwrap_or_else(Permissions::empty)
+    };
+
+    Ok(OrgAccess {
+        org_id,
+        role,
+        permissions,
+        is_global_admin: auth.is_admin,
+    })
+}
+
+/// Require the caller to hold `required` in the org
+pub async fn require_org_permission(
+    pool: &DatabasePool,
+    auth: &AuthUser,
+    org_id: Uuid,
+    required: Permissions,
+) -> Result<OrgAccess, PermissionError> {
+    let access = org_access(pool, auth, org_id).await?;
+
+    if !access.has(required) {
+        return Err(PermissionError::Forbidden(
+            "You don't have permission to do that in this organization".to_string(),
+        ));
+    }
+
+    Ok(access)
+}
+
+/// Require the caller to hold `required` in the org and to outrank the target member
+pub async fn require_member_permission(
+    pool: &DatabasePool,
+    auth: &AuthUser,
+    org_id: Uuid,
+    member_id: Uuid,
+    required: Permissions,
+) -> Result<(OrgAccess, DbMember), PermissionError> {
+    let access = require_org_permission(pool, auth, org_id, required).await?;
+
+    let member = queries::get_member_by_id(pool, member_id)
+        .await
+        .map_err(|e| PermissionError::Database(e.to_string()))?
+        .filter(|member| member.discord_org_id == org_id)
+        .ok_or(PermissionError::MemberNotFound)?;
+
+    let target_role = OrgRole::try_from(member.role.clone()).map_err(PermissionError::Database)?;
+    if !access.outranks(target_role) {
+        return Err(PermissionError::Forbidden(
+            "You can only manage members with a lower role than yours".to_string(),
+        ));
+    }
+
+    Ok((access, member))
+}
+
+/// Require a user caller to hold `permission`, or an API key caller to carry `scope`,
+/// in the org
+pub async fn require_caller_access(
+    pool: &DatabasePool,
+    caller: &Caller,
+    org_id: Uuid,
+    permission: Permissions,
+    scope: ApiKeyScope,
+) -> Result<(), PermissionError> {
+    match caller {
+        Caller::User(auth) => require_org_permission(pool, auth, org_id, permission)
+            .await
+            .map(|_| ()),
+        Caller::ApiKey(key) if key.allows(org_id, scope) => Ok(()),
+        Caller::ApiKey(_) => Err(PermissionError::Forbidden(format!(
+            "API key lacks the `{}` scope for this organization",
+            scope.as_str()
+        ))),
+    }
+}
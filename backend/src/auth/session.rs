@@ -0,0 +1,238 @@
+//! Signed session tokens
+//!
+//! After a successful Discord login the backend hands the frontend a signed
+//! JWT instead of the raw user ID. The token carries the user ID, the ID of
+//! its row in `sessions` and an expiry, and is signed with `Config::jwt_secret()`.
+//! Revoking the row (e.g. on logout) invalidates the token before it expires.
+//!
+//! Each row also records the device that logged in, so users can review their
+//! sessions and revoke the ones they don't recognise.
+
+use std::net::SocketAddr;
+
+use axum::http::{HeaderMap, header::USER_AGENT};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use serde::{Deserialize, Serialize};
+use shared::database::CreateSession;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::database::{DatabasePool, queries};
+
+/// Claims embedded in every session token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionClaims {
+    /// The authenticated user's ID
+    pub sub: Uuid,
+    /// The session row backing this token
+    pub sid: Uuid,
+    /// Issued at (unix seconds)
+    pub iat: i64,
+    /// Expires at (unix seconds)
+    pub exp: i64,
+}
+
+/// A freshly issued session token together with its expiry
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionToken {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// The device a login request came from
+#[derive(Debug, Clone, Default)]
+pub struct SessionDevice {
+    pub label: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl SessionDevice {
+    /// Describe the device from the request headers and peer address
+    pub fn from_request(headers: &HeaderMap, peer: SocketAddr) -> Self {
+        let user_agent = headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+
+        Self {
+            label: user_agent.as_deref().map(describe_user_agent),
+            user_agent,
+            ip_address: Some(client_ip(headers, peer)),
+        }
+    }
+}
+
+/// The IP address a request came from.
+///
+/// `X-Forwarded-For` is only honoured with `TRUST_PROXY_HEADERS=true`, since
+/// clients can set it to anything.
+pub fn client_ip(headers: &HeaderMap, peer: SocketAddr) -> String {
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty() && Config::trust_proxy_headers())
+        .unwrap_or_else(|| peer.ip().to_string())
+}
+
+/// Start a session for a user and issue its token using the configured secret and lifetime
+pub async fn issue_session_token(
+    pool: &DatabasePool,
+    user_id: Uuid,
+    device: SessionDevice,
+) -> Result<SessionToken, String> {
+    let create_session = CreateSession {
+        user_id,
+        device_label: device.label,
+        user_agent: device.user_agent,
+        ip_address: device.ip_address,
+        expires_at: Utc::now() + Duration::seconds(Config::session_ttl_seconds()),
+    };
+
+    let session = queries::create_session(pool, create_session)
+        .await
+        .map_err(|e| format!("Failed to save session: {}", e))?;
+
+    sign_with_secret(
+        user_id,
+        session.id,
+        session.expires_at,
+        &Config::jwt_secret(),
+    )
+}
+
+/// Build a short "Browser on OS" label from a User-Agent header
+pub fn describe_user_agent(user_agent: &str) -> String {
+    // Order matters: Edge and Opera also advertise Chrome, and Chrome advertises Safari
+    let browser = [
+        ("Edg/", "Edge"),
+        ("OPR/", "Opera"),
+        ("Firefox/", "Firefox"),
+        ("Chrome/", "Chrome"),
+        ("Safari/", "Safari"),
+    ]
+    .iter()
+    .find(|(marker, _)| user_agent.contains(marker))
+    .map(|(_, name)| *name);
+
+    let os = [
+        ("Android", "Android"),
+        ("iPhone", "iOS"),
+        ("iPad", "iPadOS"),
+        ("Windows", "Windows"),
+        ("Mac OS X", "macOS"),
+        ("Linux", "Linux"),
+    ]
+    .iter()
+    .find(|(marker, _)| user_agent.contains(marker))
+    .map(|(_, name)| *name);
+
+    match (browser, os) {
+        (Some(browser), Some(os)) => format!("{} on {}", browser, os),
+        (Some(name), None) | (None, Some(name)) => name.to_string(),
+        (None, None) => "Unknown device".to_string(),
+    }
+}
+
+/// Verify a session token's signature and expiry and return its claims
+pub fn verify_session_token(token: &str) -> Result<SessionClaims, String> {
+    verify_with_secret(token, &Config::jwt_secret())
+}
+
+fn sign_with_secret(
+    user_id: Uuid,
+    session_id: Uuid,
+    expires_at: DateTime<Utc>,
+    secret: &str,
+) -> Result<SessionToken, String> {
+    let claims = SessionClaims {
+        sub: user_id,
+        sid: session_id,
+        iat: Utc::now().timestamp(),
+        exp: expires_at.timestamp(),
+    };
+
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .map_err(|e| format!("Failed to sign session token: {}", e))?;
+
+    Ok(SessionToken { token, expires_at })
+}
+
+fn verify_with_secret(token: &str, secret: &str) -> Result<SessionClaims, String> {
+    let mut validation = Validation::default();
+    validation.leeway = 0;
+
+    decode::<SessionClaims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &validation,
+    )
+    .map(|data| data.claims)
+    .map_err(|e| format!("Invalid session token: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "test_secret";
+
+    fn in_hours(hours: i64) -> DateTime<Utc> {
+        Utc::now() + Duration::hours(hours)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let user_id = Uuid::new_v4();
+        let session_id = Uuid::new_v4();
+        let session = sign_with_secret(user_id, session_id, in_hours(1), SECRET).unwrap();
+
+        let claims = verify_with_secret(&session.token, SECRET).unwrap();
+        assert_eq!(claims.sub, user_id);
+        assert_eq!(claims.sid, session_id);
+        assert_eq!(claims.exp, session.expires_at.timestamp());
+    }
+
+    #[test]
+    fn test_describe_user_agent() {
+        assert_eq!(
+            describe_user_agent(
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:128.0) Gecko/20100101 Firefox/128.0"
+            ),
+            "Firefox on Windows"
+        );
+        assert_eq!(
+            describe_user_agent(
+                "Mozilla/5.0 (iPhone; CPU iPhone OS 17_5 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Mobile/15E148 Safari/604.1"
+            ),
+            "Safari on iOS"
+        );
+        assert_eq!(describe_user_agent("curl/8.5.0"), "Unknown device");
+    }
+
+    #[test]
+    fn test_rejects_wrong_secret() {
+        let session =
+            sign_with_secret(Uuid::new_v4(), Uuid::new_v4(), in_hours(1), SECRET).unwrap();
+        assert!(verify_with_secret(&session.token, "other_secret").is_err());
+    }
+
+    #[test]
+    fn test_rejects_expired_token() {
+        let session = sign_with_secret(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Utc::now() - Duration::seconds(10),
+            SECRET,
+        )
+        .unwrap();
+        assert!(verify_with_secret(&session.token, SECRET).is_err());
+    }
+}
@@ -39,6 +39,52 @@ impl Config {
         env::var("JWT_SECRET").unwrap_or_else(|_| "your_super_secret_jwt_key_here".to_string())
     }
 
+    /// Session token lifetime in seconds
+    pub fn session_ttl_seconds() -> i64 {
+        env::var("SESSION_TTL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(7 * 24 * 60 * 60)
+    }
+
+    /// Whether to take client IPs from `X-Forwarded-For` (only behind a trusted proxy)
+    pub fn trust_proxy_headers() -> bool {
+        env::var("TRUST_PROXY_HEADERS")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false)
+    }
+
+    /// Discord IDs of users with global admin rights
+    pub fn admin_discord_ids() -> Vec<String> {
+        env::var("ADMIN_DISCORD_IDS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    // Token encryption configuration
+    /// Keys for encrypting Discord tokens at rest, as `key_id:base64_key` pairs
+    pub fn token_encryption_keys() -> Vec<(String, String)> {
+        env::var("TOKEN_ENCRYPTION_KEYS")
+            .unwrap_or_else(|_| "dev:ZGV2X29ubHlfdG9rZW5fZW5jcnlwdGlvbl9rZXlfMzI=".to_string())
+            .split(',')
+            .filter_map(|pair| pair.trim().split_once(':'))
+            .map(|(key_id, key)| (key_id.trim().to_string(), key.trim().to_string()))
+            .collect()
+    }
+
+    /// ID of the key used to encrypt new tokens (defaults to the first configured key)
+    pub fn token_encryption_active_key_id() -> Option<String> {
+        env::var("TOKEN_ENCRYPTION_ACTIVE_KEY_ID").ok()
+    }
+
+    /// Secret for the keyed hash used to look up tokens
+    pub fn token_hash_key() -> String {
+        env::var("TOKEN_HASH_KEY").unwrap_or_else(|_| "your_token_hash_key_here".to_string())
+    }
+
     // Discord OAuth configuration
     /// Discord OAuth client ID
     pub fn discord_client_id() -> String {
@@ -56,6 +102,46 @@ impl Config {
             .unwrap_or_else(|_| "http://localhost:8081/auth/discord/callback".to_string())
     }
 
+    /// How long an issued OAuth state value stays valid, in seconds
+    pub fn oauth_state_ttl_seconds() -> i64 {
+        env::var("OAUTH_STATE_TTL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(10 * 60)
+    }
+
+    /// How many unused OAuth states one client IP can hold at once
+    pub fn oauth_state_max_per_client() -> i64 {
+        env::var("OAUTH_STATE_MAX_PER_CLIENT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(10)
+    }
+
+    /// How many unused OAuth states can be outstanding across all clients
+    pub fn oauth_state_max_pending() -> i64 {
+        env::var("OAUTH_STATE_MAX_PENDING")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(10_000)
+    }
+
+    /// How long a nominated org owner has to accept the transfer, in seconds
+    pub fn ownership_transfer_ttl_seconds() -> i64 {
+        env::var("OWNERSHIP_TRANSFER_TTL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(48 * 60 * 60)
+    }
+
+    /// How long participants have to confirm or dispute a reported match, in seconds
+    pub fn match_confirmation_window_seconds() -> i64 {
+        env::var("MATCH_CONFIRMATION_WINDOW_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(24 * 60 * 60)
+    }
+
     // Frontend URLs
     /// Frontend base URL
     pub fn frontend_url() -> String {
@@ -85,12 +171,24 @@ impl Config {
     }
 
     // Discord API URLs
+    /// Discord OAuth authorization page URL
+    pub fn discord_authorize_url() -> String {
+        env::var("DISCORD_AUTHORIZE_URL")
+            .unwrap_or_else(|_| "https://discord.com/oauth2/authorize".to_string())
+    }
+
     /// Discord OAuth token exchange URL
     pub fn discord_token_url() -> String {
         env::var("DISCORD_TOKEN_URL")
             .unwrap_or_else(|_| "https://discord.com/api/oauth2/token".to_string())
     }
 
+    /// Discord OAuth2 token revocation URL
+    pub fn discord_revoke_url() -> String {
+        env::var("DISCORD_REVOKE_URL")
+            .unwrap_or_else(|_| "https://discord.com/api/oauth2/token/revoke".to_string())
+    }
+
     /// Discord user info API URL
     pub fn discord_user_api_url() -> String {
         env::var("DISCORD_USER_API_URL")
@@ -102,6 +200,63 @@ impl Config {
         env::var("DISCORD_API_BASE_URL").unwrap_or_else(|_| "https://discord.com/api".to_string())
     }
 
+    // Background task configuration
+    /// How often the Discord token refresh task runs, in seconds
+    pub fn token_refresh_interval_seconds() -> u64 {
+        env::var("TOKEN_REFRESH_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5 * 60)
+    }
+
+    /// Refresh Discord tokens that expire within this many seconds
+    pub fn token_refresh_window_seconds() -> i64 {
+        env::var("TOKEN_REFRESH_WINDOW_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60 * 60)
+    }
+
+    /// How often expired member bans are lifted, in seconds
+    pub fn ban_sweep_interval_seconds() -> u64 {
+        env::var("BAN_SWEEP_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60)
+    }
+
+    /// How often unconfirmed matches past their confirmation window are finalized, in seconds
+    pub fn match_finalize_interval_seconds() -> u64 {
+        env::var("MATCH_FINALIZE_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60)
+    }
+
+    /// How often seasons that have ended are archived and their ratings soft reset, in seconds
+    pub fn season_rollover_interval_seconds() -> u64 {
+        env::var("SEASON_ROLLOVER_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60)
+    }
+
+    /// How often soft-deleted users and orgs past the retention window are purged, in seconds
+    pub fn purge_interval_seconds() -> u64 {
+        env::var("PURGE_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60 * 60)
+    }
+
+    /// How long deleted users and orgs can be restored before they are purged, in days
+    pub fn soft_delete_retention_days() -> i64 {
+        env::var("SOFT_DELETE_RETENTION_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30)
+    }
+
     // CORS configuration
     /// Allowed CORS origins
     pub fn allowed_origins() -> Vec<String> {
@@ -161,9 +316,32 @@ impl Config {
         )
     }
 
+    /// Build the Discord authorization URL for a login started by the backend, which
+    /// requests the same scopes as the frontend flow
+    pub fn discord_oauth_url(state: &str, code_challenge: &str) -> Result<String, String> {
+        reqwest::Url::parse_with_params(
+            &Self::discord_authorize_url(),
+            &[
+                ("client_id", Self::discord_client_id().as_str()),
+                ("response_type", "code"),
+                ("redirect_uri", Self::discord_redirect_uri().as_str()),
+                ("scope", "identify guilds"),
+                ("state", state),
+                ("code_challenge", code_challenge),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map(|url| url.to_string())
+        .map_err(|e| format!("Invalid Discord authorize URL: {}", e))
+    }
+
     /// Get success redirect URL for OAuth
-    pub fn oauth_success_redirect(user_id: &str) -> String {
-        format!("{}/?auth=success&user_id={}", Self::frontend_url(), user_id)
+    pub fn oauth_success_redirect(session_token: &str) -> String {
+        format!(
+            "{}/?auth=success&session_token={}",
+            Self::frontend_url(),
+            session_token
+        )
     }
 
     /// Get error redirect URL for OAuth
@@ -200,6 +378,14 @@ impl Config {
             errors.push("JWT_SECRET should be set in production".to_string());
         }
 
+        if env::var("TOKEN_ENCRYPTION_KEYS").is_err() && Self::is_production() {
+            errors.push("TOKEN_ENCRYPTION_KEYS is required in production".to_string());
+        }
+
+        if env::var("TOKEN_HASH_KEY").is_err() && Self::is_production() {
+            errors.push("TOKEN_HASH_KEY is required in production".to_string());
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -269,8 +455,8 @@ mod tests {
         }
 
         assert_eq!(
-            Config::oauth_success_redirect("user123"),
-            "https://app.example.com/?auth=success&user_id=user123"
+            Config::oauth_success_redirect("token123"),
+            "https://app.example.com/?auth=success&session_token=token123"
         );
 
         assert_eq!(
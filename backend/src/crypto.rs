@@ -0,0 +1,176 @@
+//! Encryption of Discord credentials at rest
+//!
+//! Access and refresh tokens are sealed with AES-256-GCM before they are written
+//! to `discord_tokens`. Every row records the ID of the key that sealed it, so
+//! new keys can be introduced via `TOKEN_ENCRYPTION_KEYS` and existing rows
+//! re-encrypted with `backend rotate-token-keys`. Lookups by access token go
+//! through an HMAC-SHA256 keyed hash instead of the plaintext.
+
+use std::collections::HashMap;
+
+use aes_gcm::{
+    Aes256Gcm, KeyInit, Nonce,
+    aead::{Aead, AeadCore, OsRng, Payload},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::config::Config;
+
+const NONCE_LENGTH: usize = 12;
+
+/// Seals and opens token values with the configured keys
+pub struct TokenCipher {
+    keys: HashMap<String, Aes256Gcm>,
+    active_key_id: String,
+    hash_key: Vec<u8>,
+}
+
+impl TokenCipher {
+    /// Build a cipher from `TOKEN_ENCRYPTION_KEYS`, `TOKEN_ENCRYPTION_ACTIVE_KEY_ID` and `TOKEN_HASH_KEY`
+    pub fn from_config() -> Result<Self, String> {
+        let keys = Config::token_encryption_keys();
+        let active_key_id = Config::token_encryption_active_key_id()
+            .or_else(|| keys.first().map(|(key_id, _)| key_id.clone()))
+            .ok_or("No token encryption keys configured")?;
+
+        Self::new(&keys, &active_key_id, Config::token_hash_key().as_bytes())
+    }
+
+    /// Build a cipher from `(key_id, base64 key)` pairs
+    pub fn new(keys: &[(String, String)], active_key_id: &str, hash_key: &[u8]) -> Result<Self, String> {
+        let mut ciphers = HashMap::new();
+        for (key_id, encoded_key) in keys {
+            let key = STANDARD
+                .decode(encoded_key)
+                .map_err(|e| format!("Token encryption key '{}' is not base64: {}", key_id, e))?;
+            let cipher = Aes256Gcm::new_from_slice(&key)
+                .map_err(|_| format!("Token encryption key '{}' must be 32 bytes", key_id))?;
+            ciphers.insert(key_id.clone(), cipher);
+        }
+
+        if !ciphers.contains_key(active_key_id) {
+            return Err(format!(
+                "Active token encryption key '{}' is not configured",
+                active_key_id
+            ));
+        }
+
+        Ok(Self {
+            keys: ciphers,
+            active_key_id: active_key_id.to_string(),
+            hash_key: hash_key.to_vec(),
+        })
+    }
+
+    /// ID of the key new values are sealed with
+    pub fn active_key_id(&self) -> &str {
+        &self.active_key_id
+    }
+
+    /// Seal a value with the active key. `field` is bound as associated data so a
+    /// ciphertext can't be moved between columns.
+    pub fn encrypt(&self, field: &str, plaintext: &str) -> Result<String, String> {
+        let cipher = &self.keys[&self.active_key_id];
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: field.as_bytes(),
+                },
+            )
+            .map_err(|_| "Token encryption failed".to_string())?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(STANDARD.encode(sealed))
+    }
+
+    /// Open a value sealed with the given key
+    pub fn decrypt(&self, key_id: &str, field: &str, sealed: &str) -> Result<String, String> {
+        let cipher = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| format!("Token encryption key '{}' is not configured", key_id))?;
+
+        let sealed = STANDARD
+            .decode(sealed)
+            .map_err(|e| format!("Sealed token is not base64: {}", e))?;
+        if sealed.len() < NONCE_LENGTH {
+            return Err("Sealed token is too short".to_string());
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LENGTH);
+
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: field.as_bytes(),
+                },
+            )
+            .map_err(|_| "Token decryption failed".to_string())?;
+
+        String::from_utf8(plaintext).map_err(|e| format!("Decrypted token is not UTF-8: {}", e))
+    }
+
+    /// Keyed hash used to look tokens up without storing them in plaintext
+    pub fn hash(&self, value: &str) -> String {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.hash_key)
+            .expect("HMAC accepts keys of any size");
+        mac.update(value.as_bytes());
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> String {
+        STANDARD.encode([byte; 32])
+    }
+
+    #[test]
+    fn test_round_trip_and_rotation() {
+        let old = TokenCipher::new(&[("k1".to_string(), key(1))], "k1", b"hash").unwrap();
+        let sealed = old.encrypt("access_token", "secret").unwrap();
+        assert_ne!(sealed, "secret");
+
+        // After rotation the old key stays available for decryption
+        let rotated = TokenCipher::new(
+            &[("k1".to_string(), key(1)), ("k2".to_string(), key(2))],
+            "k2",
+            b"hash",
+        )
+        .unwrap();
+        assert_eq!(rotated.decrypt("k1", "access_token", &sealed).unwrap(), "secret");
+
+        let resealed = rotated.encrypt("access_token", "secret").unwrap();
+        assert_eq!(rotated.decrypt("k2", "access_token", &resealed).unwrap(), "secret");
+        assert!(rotated.decrypt("k1", "access_token", &resealed).is_err());
+    }
+
+    #[test]
+    fn test_field_is_authenticated() {
+        let cipher = TokenCipher::new(&[("k1".to_string(), key(1))], "k1", b"hash").unwrap();
+        let sealed = cipher.encrypt("access_token", "secret").unwrap();
+        assert!(cipher.decrypt("k1", "refresh_token", &sealed).is_err());
+    }
+
+    #[test]
+    fn test_hash_is_keyed() {
+        let a = TokenCipher::new(&[("k1".to_string(), key(1))], "k1", b"a").unwrap();
+        let b = TokenCipher::new(&[("k1".to_string(), key(1))], "k1", b"b").unwrap();
+        assert_eq!(a.hash("token"), a.hash("token"));
+        assert_ne!(a.hash("token"), b.hash("token"));
+    }
+}
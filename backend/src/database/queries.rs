@@ -2,12 +2,19 @@ use sqlx::Type;
 use uuid::Uuid;
 
 use shared::database::{
-    CreateDiscordOrg, CreateDiscordToken, CreateMember, CreateUser, DbDiscordOrg, DbDiscordToken,
-    DbMember, DbMemberWithRelations, DbUser, UpdateDiscordOrg, UpdateDiscordToken, UpdateMember,
+    CreateApiKey, CreateDiscordOrg, CreateDiscordToken, CreateJoinRequest, CreateMatch,
+    CreateMatchParticipant, CreateMember, CreateMemberBan, CreateOrgInvite,
+    CreateOwnershipTransfer, CreateSeason, CreateSession, CreateUser, DbApiKey, DbDiscordOrg,
+    DbDiscordToken, DbInviteRedemptionWithUser, DbJoinRequest, DbLeaderboardEntry, DbMatch,
+    DbMatchParticipant, DbMember, DbMemberBan, DbMemberStatusChange, DbMemberWithRelations,
+    DbOAuthState, DbOrgInvite, DbOrgListing, DbOwnershipTransfer, DbRating, DbRatingHistory,
+    DbSeason, DbSession, DbUser, UpdateDiscordOrg, UpdateDiscordToken, UpdateMatch, UpdateMember,
     UpdateUser,
 };
-use shared::models::MemberStatus;
+use shared::models::{JoinRequestStatus, MatchConfirmation, MatchStatus, MemberStatus, OrgRole};
+use shared::rating::{self, Glicko2, Rating};
 
+use crate::crypto::TokenCipher;
 use crate::database::DatabasePool;
 
 // Custom type for PostgreSQL enum
@@ -29,6 +36,26 @@ impl From<MemberStatus> for PgMemberStatus {
     }
 }
 
+#[derive(Debug, Clone, Type)]
+#[sqlx(type_name = "org_role", rename_all = "lowercase")]
+enum PgOrgRole {
+    Owner,
+    Admin,
+    Moderator,
+    Member,
+}
+
+impl From<OrgRole> for PgOrgRole {
+    fn from(role: OrgRole) -> Self {
+        match role {
+            OrgRole::Owner => PgOrgRole::Owner,
+            OrgRole::Admin => PgOrgRole::Admin,
+            OrgRole::Moderator => PgOrgRole::Moderator,
+            OrgRole::Member => PgOrgRole::Member,
+        }
+    }
+}
+
 impl From<PgMemberStatus> for MemberStatus {
     fn from(status: PgMemberStatus) -> Self {
         match status {
@@ -68,7 +95,7 @@ pub async fn get_user_by_id(
 ) -> Result<Option<DbUser>, sqlx::Error> {
     let user = sqlx::query_as!(
         DbUser,
-        "SELECT id, discord_id, display_name, avatar_url, bio, created_at, updated_at FROM users WHERE id = $1",
+        "SELECT id, discord_id, display_name, avatar_url, bio, created_at, updated_at FROM users WHERE id = $1 AND deleted_at IS NULL",
         user_id
     )
     .fetch_optional(pool)
@@ -83,7 +110,7 @@ pub async fn get_user_by_discord_id(
 ) -> Result<Option<DbUser>, sqlx::Error> {
     let user = sqlx::query_as!(
         DbUser,
-        "SELECT id, discord_id, display_name, avatar_url, bio, created_at, updated_at FROM users WHERE discord_id = $1",
+        "SELECT id, discord_id, display_name, avatar_url, bio, created_at, updated_at FROM users WHERE discord_id = $1 AND deleted_at IS NULL",
         discord_id
     )
     .fetch_optional(pool)
@@ -106,7 +133,7 @@ pub async fn update_user(
             avatar_url = COALESCE($3, avatar_url),
             bio = COALESCE($4, bio),
             updated_at = NOW()
-        WHERE id = $1
+        WHERE id = $1 AND deleted_at IS NULL
         RETURNING id, discord_id, display_name, avatar_url, bio, created_at, updated_at
         "#,
         user_id,
@@ -120,12 +147,111 @@ pub async fn update_user(
     Ok(user)
 }
 
+/// Soft-delete a user together with the orgs they own and revoke their sessions; the
+/// rows stay restorable until [`purge_deleted`] removes them
 pub async fn delete_user(pool: &DatabasePool, user_id: Uuid) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query!("DELETE FROM users WHERE id = $1", user_id)
-        .execute(pool)
-        .await?;
+    let mut tx = pool.begin().await?;
 
-    Ok(result.rows_affected() > 0)
+    let deleted_at = sqlx::query_scalar!(
+        r#"UPDATE users SET deleted_at = NOW() WHERE id = $1 AND deleted_at IS NULL RETURNING deleted_at as "deleted_at!""#,
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(deleted_at) = deleted_at else {
+        return Ok(false);
+    };
+
+    // Stamped with the user's deletion time so restoring the user brings these back
+    sqlx::query!(
+        "UPDATE discord_orgs SET deleted_at = $2 WHERE owner_id = $1 AND deleted_at IS NULL",
+        user_id,
+        deleted_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE sessions SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Credentials stop working right away rather than at purge time, and stay
+    // revoked if the user is restored
+    sqlx::query!(
+        "UPDATE api_keys SET revoked_at = NOW() WHERE created_by = $1 AND revoked_at IS NULL",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE discord_tokens SET invalidated_at = NOW(), updated_at = NOW() WHERE user_id = $1 AND invalidated_at IS NULL",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// Restore a user deleted after `deleted_since`, along with the orgs deleted with them
+/// (except those whose guild has been imported again by another org in the meantime)
+pub async fn restore_user(
+    pool: &DatabasePool,
+    user_id: Uuid,
+    deleted_since: chrono::DateTime<chrono::Utc>,
+) -> Result<Option<DbUser>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let deleted_at = sqlx::query_scalar!(
+        r#"SELECT deleted_at as "deleted_at!" FROM users WHERE id = $1 AND deleted_at > $2 FOR UPDATE"#,
+        user_id,
+        deleted_since
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(deleted_at) = deleted_at else {
+        return Ok(None);
+    };
+
+    let user = sqlx::query_as!(
+        DbUser,
+        r#"
+        UPDATE users SET deleted_at = NULL, updated_at = NOW()
+        WHERE id = $1
+        RETURNING id, discord_id, display_name, avatar_url, bio, created_at, updated_at
+        "#,
+        user_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE discord_orgs SET deleted_at = NULL
+        WHERE owner_id = $1
+          AND deleted_at = $2
+          AND NOT EXISTS (
+              SELECT 1 FROM discord_orgs live
+              WHERE live.guild_id = discord_orgs.guild_id AND live.deleted_at IS NULL
+          )
+        "#,
+        user_id,
+        deleted_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Some(user))
 }
 
 pub async fn list_users(
@@ -135,7 +261,7 @@ pub async fn list_users(
 ) -> Result<Vec<DbUser>, sqlx::Error> {
     let users = sqlx::query_as!(
         DbUser,
-        "SELECT id, discord_id, display_name, avatar_url, bio, created_at, updated_at FROM users ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+        "SELECT id, discord_id, display_name, avatar_url, bio, created_at, updated_at FROM users WHERE deleted_at IS NULL ORDER BY created_at DESC LIMIT $1 OFFSET $2",
         limit,
         offset
     )
@@ -146,25 +272,55 @@ pub async fn list_users(
 }
 
 // Discord org queries
+/// Create an org together with its owner's membership
 pub async fn create_discord_org(
     pool: &DatabasePool,
     create_org: CreateDiscordOrg,
 ) -> Result<DbDiscordOrg, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
     let org = sqlx::query_as!(
         DbDiscordOrg,
         r#"
-        INSERT INTO discord_orgs (owner_id, name, avatar_url, description)
-        VALUES ($1, $2, $3, $4)
-        RETURNING id, owner_id, name, avatar_url, description, created_at, updated_at
+        INSERT INTO discord_orgs (owner_id, name, avatar_url, description, guild_id, requires_approval, listed, tags)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id, owner_id, name, avatar_url, description, guild_id, requires_approval, listed, tags, created_at, updated_at
         "#,
         create_org.owner_id,
         create_org.name,
         create_org.avatar_url,
-        create_org.description
+        create_org.description,
+        create_org.guild_id,
+        create_org.requires_approval,
+        create_org.listed,
+        &create_org.tags
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    let owner_member = sqlx::query!(
+        r#"
+        INSERT INTO members (user_id, discord_org_id, role) VALUES ($1, $2, 'owner')
+        RETURNING id, status as "status: String"
+        "#,
+        org.owner_id,
+        org.id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record_status_change(
+        &mut tx,
+        owner_member.id,
+        None,
+        &owner_member.status,
+        Some(org.owner_id),
+        None,
+    )
+    .await?;
+
+    tx.commit().await?;
+
     Ok(org)
 }
 
@@ -174,7 +330,7 @@ pub async fn get_discord_org_by_id(
 ) -> Result<Option<DbDiscordOrg>, sqlx::Error> {
     let org = sqlx::query_as!(
         DbDiscordOrg,
-        "SELECT id, owner_id, name, avatar_url, description, created_at, updated_at FROM discord_orgs WHERE id = $1",
+        "SELECT id, owner_id, name, avatar_url, description, guild_id, requires_approval, listed, tags, created_at, updated_at FROM discord_orgs WHERE id = $1 AND deleted_at IS NULL",
         org_id
     )
     .fetch_optional(pool)
@@ -183,13 +339,29 @@ pub async fn get_discord_org_by_id(
     Ok(org)
 }
 
+/// Get the orgs bound to any of the given Discord guilds
+pub async fn get_discord_orgs_by_guild_ids(
+    pool: &DatabasePool,
+    guild_ids: &[String],
+) -> Result<Vec<DbDiscordOrg>, sqlx::Error> {
+    let orgs = sqlx::query_as!(
+        DbDiscordOrg,
+        "SELECT id, owner_id, name, avatar_url, description, guild_id, requires_approval, listed, tags, created_at, updated_at FROM discord_orgs WHERE guild_id = ANY($1) AND deleted_at IS NULL",
+        guild_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(orgs)
+}
+
 pub async fn get_discord_orgs_by_owner(
     pool: &DatabasePool,
     owner_id: Uuid,
 ) -> Result<Vec<DbDiscordOrg>, sqlx::Error> {
     let orgs = sqlx::query_as!(
         DbDiscordOrg,
-        "SELECT id, owner_id, name, avatar_url, description, created_at, updated_at FROM discord_orgs WHERE owner_id = $1 ORDER BY created_at DESC",
+        "SELECT id, owner_id, name, avatar_url, description, guild_id, requires_approval, listed, tags, created_at, updated_at FROM discord_orgs WHERE owner_id = $1 AND deleted_at IS NULL ORDER BY created_at DESC",
         owner_id
     )
     .fetch_all(pool)
@@ -211,14 +383,20 @@ pub async fn update_discord_org(
             name = COALESCE($2, name),
             avatar_url = COALESCE($3, avatar_url),
             description = COALESCE($4, description),
+            requires_approval = COALESCE($5, requires_approval),
+            listed = COALESCE($6, listed),
+            tags = COALESCE($7, tags),
             updated_at = NOW()
-        WHERE id = $1
-        RETURNING id, owner_id, name, avatar_url, description, created_at, updated_at
+        WHERE id = $1 AND deleted_at IS NULL
+        RETURNING id, owner_id, name, avatar_url, description, guild_id, requires_approval, listed, tags, created_at, updated_at
         "#,
         org_id,
         update_org.name,
         update_org.avatar_url,
-        update_org.description
+        update_org.description,
+        update_org.requires_approval,
+        update_org.listed,
+        update_org.tags.as_deref()
     )
     .fetch_optional(pool)
     .await?;
@@ -226,14 +404,96 @@ pub async fn update_discord_org(
     Ok(org)
 }
 
+/// Soft-delete an org; it stays restorable until [`purge_deleted`] removes it
 pub async fn delete_discord_org(pool: &DatabasePool, org_id: Uuid) -> Result<bool, sqlx::Error> {
-    let result = sqlx::query!("DELETE FROM discord_orgs WHERE id = $1", org_id)
-        .execute(pool)
-        .await?;
+    let result = sqlx::query!(
+        "UPDATE discord_orgs SET deleted_at = NOW() WHERE id = $1 AND deleted_at IS NULL",
+        org_id
+    )
+    .execute(pool)
+    .await?;
 
     Ok(result.rows_affected() > 0)
 }
 
+/// Restore an org deleted after `deleted_since`; orgs whose owner is still deleted
+/// stay deleted
+pub async fn restore_discord_org(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    deleted_since: chrono::DateTime<chrono::Utc>,
+) -> Result<Option<DbDiscordOrg>, sqlx::Error> {
+    let org = sqlx::query_as!(
+        DbDiscordOrg,
+        r#"
+        UPDATE discord_orgs SET deleted_at = NULL, updated_at = NOW()
+        WHERE id = $1
+          AND deleted_at > $2
+          AND owner_id IN (SELECT id FROM users WHERE deleted_at IS NULL)
+        RETURNING id, owner_id, name, avatar_url, description, guild_id, requires_approval, listed, tags, created_at, updated_at
+        "#,
+        org_id,
+        deleted_since
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(org)
+}
+
+/// Search the orgs listed in the public directory. `query` is matched with Postgres
+/// full-text search; `sort` is `relevance`, `members` or `newest`.
+pub async fn search_listed_discord_orgs(
+    pool: &DatabasePool,
+    query: Option<&str>,
+    tag: Option<&str>,
+    sort: &str,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<DbOrgListing>, sqlx::Error> {
+    let orgs = sqlx::query_as!(
+        DbOrgListing,
+        r#"
+        SELECT
+            o.id, o.owner_id, o.name, o.avatar_url, o.description, o.guild_id, o.requires_approval, o.listed, o.tags, o.created_at, o.updated_at,
+            u.discord_id as owner_discord_id,
+            u.display_name as owner_display_name,
+            u.avatar_url as owner_avatar_url,
+            u.bio as owner_bio,
+            u.created_at as owner_created_at,
+            u.updated_at as owner_updated_at,
+            mc.member_count as "member_count!"
+        FROM discord_orgs o
+        JOIN users u ON u.id = o.owner_id
+        -- Same count as `count_members_by_org`
+        CROSS JOIN LATERAL (
+            SELECT COUNT(*) as member_count
+            FROM members m
+            JOIN users mu ON mu.id = m.user_id
+            WHERE m.discord_org_id = o.id AND m.status <> 'banned' AND mu.deleted_at IS NULL
+        ) mc
+        WHERE o.listed
+          AND o.deleted_at IS NULL
+          AND ($1::text IS NULL OR o.search_vector @@ websearch_to_tsquery('english', $1))
+          AND ($2::text IS NULL OR $2 = ANY(o.tags))
+        ORDER BY
+            CASE WHEN $3 = 'members' THEN mc.member_count END DESC NULLS LAST,
+            CASE WHEN $3 = 'relevance' AND $1::text IS NOT NULL THEN ts_rank(o.search_vector, websearch_to_tsquery('english', $1)) END DESC NULLS LAST,
+            o.created_at DESC
+        LIMIT $4 OFFSET $5
+        "#,
+        query,
+        tag,
+        sort,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(orgs)
+}
+
 pub async fn list_discord_orgs(
     pool: &DatabasePool,
     limit: i64,
@@ -241,7 +501,7 @@ pub async fn list_discord_orgs(
 ) -> Result<Vec<DbDiscordOrg>, sqlx::Error> {
     let orgs = sqlx::query_as!(
         DbDiscordOrg,
-        "SELECT id, owner_id, name, avatar_url, description, created_at, updated_at FROM discord_orgs ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+        "SELECT id, owner_id, name, avatar_url, description, guild_id, requires_approval, listed, tags, created_at, updated_at FROM discord_orgs WHERE deleted_at IS NULL ORDER BY created_at DESC LIMIT $1 OFFSET $2",
         limit,
         offset
     )
@@ -252,36 +512,184 @@ pub async fn list_discord_orgs(
 }
 
 // Member queries
+/// SQLSTATE raised by the `reject_banned_member_insert` trigger
+const MEMBER_BANNED_SQLSTATE: &str = "BAN01";
+
+/// Whether an insert into `members` failed because the user is banned from the org
+pub fn is_member_banned_error(e: &sqlx::Error) -> bool {
+    e.as_database_error()
+        .and_then(|e| e.code())
+        .is_some_and(|code| code == MEMBER_BANNED_SQLSTATE)
+}
+
+/// Create a membership; fails with [`is_member_banned_error`] while the user has an
+/// active ban in the org
 pub async fn create_member(
     pool: &DatabasePool,
     create_member: CreateMember,
+) -> Result<DbMember, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let member = insert_member(&mut tx, create_member).await?;
+    tx.commit().await?;
+
+    Ok(member)
+}
+
+/// Insert a member and the first entry of their status history
+async fn insert_member(
+    conn: &mut sqlx::PgConnection,
+    create_member: CreateMember,
 ) -> Result<DbMember, sqlx::Error> {
     let pg_status: PgMemberStatus = create_member.status.into();
+    let pg_role: PgOrgRole = create_member.role.into();
 
     let member = sqlx::query_as!(
         DbMember,
         r#"
-        INSERT INTO members (user_id, discord_org_id, status)
-        VALUES ($1, $2, $3)
-        RETURNING id, user_id, discord_org_id, status as "status: String", created_at, updated_at
+        INSERT INTO members (user_id, discord_org_id, status, role)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, user_id, discord_org_id, status as "status: String", role as "role: String", created_at, updated_at
         "#,
         create_member.user_id,
         create_member.discord_org_id,
-        pg_status as PgMemberStatus
+        pg_status as PgMemberStatus,
+        pg_role as PgOrgRole
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    record_status_change(
+        conn,
+        member.id,
+        None,
+        &member.status,
+        Some(member.user_id),
+        None,
     )
-    .fetch_one(pool)
     .await?;
 
     Ok(member)
 }
 
+/// Append an entry to a member's status history
+async fn record_status_change(
+    conn: &mut sqlx::PgConnection,
+    member_id: Uuid,
+    from_status: Option<&str>,
+    to_status: &str,
+    changed_by: Option<Uuid>,
+    reason: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO member_status_history (member_id, from_status, to_status, changed_by, reason)
+        VALUES ($1, $2::text::member_status, $3::text::member_status, $4, $5)
+        "#,
+        member_id,
+        from_status,
+        to_status,
+        changed_by,
+        reason
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Outcome of changing a member's status
+#[derive(Debug)]
+pub enum StatusChange {
+    Changed(DbMember),
+    NotFound,
+    NotAllowed(MemberStatus), // The member's current status, which can't move to the requested one
+}
+
+/// Change a member's status if [`MemberStatus::can_transition_to`] allows it, recording
+/// the change in their history
+pub async fn change_member_status(
+    pool: &DatabasePool,
+    member_id: Uuid,
+    to_status: MemberStatus,
+    changed_by: Option<Uuid>,
+    reason: Option<&str>,
+) -> Result<StatusChange, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let current = sqlx::query_scalar!(
+        r#"SELECT status as "status: String" FROM members WHERE id = $1 FOR UPDATE"#,
+        member_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(current) = current else {
+        return Ok(StatusChange::NotFound);
+    };
+    let from_status =
+        MemberStatus::try_from(current.clone()).map_err(|e| sqlx::Error::Decode(e.into()))?;
+    if !from_status.can_transition_to(&to_status) {
+        return Ok(StatusChange::NotAllowed(from_status));
+    }
+
+    let pg_status: PgMemberStatus = to_status.into();
+    let member = sqlx::query_as!(
+        DbMember,
+        r#"
+        UPDATE members
+        SET status = $2, updated_at = NOW()
+        WHERE id = $1
+        RETURNING id, user_id, discord_org_id, status as "status: String", role as "role: String", created_at, updated_at
+        "#,
+        member_id,
+        pg_status as PgMemberStatus
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record_status_change(
+        &mut tx,
+        member_id,
+        Some(&current),
+        &member.status,
+        changed_by,
+        reason,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(StatusChange::Changed(member))
+}
+
+/// Get a member's status history, oldest first
+pub async fn get_member_status_history(
+    pool: &DatabasePool,
+    member_id: Uuid,
+) -> Result<Vec<DbMemberStatusChange>, sqlx::Error> {
+    let history = sqlx::query_as!(
+        DbMemberStatusChange,
+        r#"
+        SELECT id, member_id, from_status as "from_status: String", to_status as "to_status: String", changed_by, reason, changed_at
+        FROM member_status_history
+        WHERE member_id = $1
+        ORDER BY changed_at
+        "#,
+        member_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(history)
+}
+
 pub async fn get_member_by_id(
     pool: &DatabasePool,
     member_id: Uuid,
 ) -> Result<Option<DbMember>, sqlx::Error> {
     let member = sqlx::query_as!(
         DbMember,
-        r#"SELECT id, user_id, discord_org_id, status as "status: String", created_at, updated_at FROM members WHERE id = $1"#,
+        r#"SELECT id, user_id, discord_org_id, status as "status: String", role as "role: String", created_at, updated_at FROM members WHERE id = $1"#,
         member_id
     )
     .fetch_optional(pool)
@@ -300,6 +708,7 @@ pub async fn get_member_with_relations(
         SELECT
             m.id,
             m.status as "status: String",
+            m.role as "role: String",
             m.created_at,
             m.updated_at,
 
@@ -316,6 +725,10 @@ pub async fn get_member_with_relations(
             o.name as org_name,
             o.avatar_url as org_avatar_url,
             o.description as org_description,
+            o.guild_id as org_guild_id,
+            o.requires_approval as org_requires_approval,
+            o.listed as org_listed,
+            o.tags as org_tags,
             o.created_at as org_created_at,
             o.updated_at as org_updated_at,
 
@@ -339,16 +752,21 @@ pub async fn get_member_with_relations(
     Ok(member)
 }
 
+/// Get an org's members, optionally only those with the given status
 pub async fn get_members_by_org(
     pool: &DatabasePool,
     org_id: Uuid,
+    status: Option<MemberStatus>,
 ) -> Result<Vec<DbMemberWithRelations>, sqlx::Error> {
+    let pg_status = status.map(|s| -> PgMemberStatus { s.into() });
+
     let members = sqlx::query_as!(
         DbMemberWithRelations,
         r#"
         SELECT
             m.id,
             m.status as "status: String",
+            m.role as "role: String",
             m.created_at,
             m.updated_at,
 
@@ -365,6 +783,10 @@ pub async fn get_members_by_org(
             o.name as org_name,
             o.avatar_url as org_avatar_url,
             o.description as org_description,
+            o.guild_id as org_guild_id,
+            o.requires_approval as org_requires_approval,
+            o.listed as org_listed,
+            o.tags as org_tags,
             o.created_at as org_created_at,
             o.updated_at as org_updated_at,
 
@@ -378,10 +800,11 @@ pub async fn get_members_by_org(
         JOIN users u ON m.user_id = u.id
         JOIN discord_orgs o ON m.discord_org_id = o.id
         JOIN users owner ON o.owner_id = owner.id
-        WHERE m.discord_org_id = $1
+        WHERE m.discord_org_id = $1 AND ($2::member_status IS NULL OR m.status = $2) AND u.deleted_at IS NULL
         ORDER BY m.created_at ASC
         "#,
-        org_id
+        org_id,
+        pg_status as Option<PgMemberStatus>
     )
     .fetch_all(pool)
     .await?;
@@ -399,6 +822,7 @@ pub async fn get_members_by_user(
         SELECT
             m.id,
             m.status as "status: String",
+            m.role as "role: String",
             m.created_at,
             m.updated_at,
 
@@ -415,6 +839,10 @@ pub async fn get_members_by_user(
             o.name as org_name,
             o.avatar_url as org_avatar_url,
             o.description as org_description,
+            o.guild_id as org_guild_id,
+            o.requires_approval as org_requires_approval,
+            o.listed as org_listed,
+            o.tags as org_tags,
             o.created_at as org_created_at,
             o.updated_at as org_updated_at,
 
@@ -428,7 +856,7 @@ pub async fn get_members_by_user(
         JOIN users u ON m.user_id = u.id
         JOIN discord_orgs o ON m.discord_org_id = o.id
         JOIN users owner ON o.owner_id = owner.id
-        WHERE m.user_id = $1
+        WHERE m.user_id = $1 AND o.deleted_at IS NULL
         ORDER BY m.created_at ASC
         "#,
         user_id
@@ -445,6 +873,7 @@ pub async fn update_member(
     update_member: UpdateMember,
 ) -> Result<Option<DbMember>, sqlx::Error> {
     let pg_status = update_member.status.map(|s| -> PgMemberStatus { s.into() });
+    let pg_role = update_member.role.map(|r| -> PgOrgRole { r.into() });
 
     let member = sqlx::query_as!(
         DbMember,
@@ -452,12 +881,14 @@ pub async fn update_member(
         UPDATE members
         SET
             status = COALESCE($2, status),
+            role = COALESCE($3, role),
             updated_at = NOW()
         WHERE id = $1
-        RETURNING id, user_id, discord_org_id, status as "status: String", created_at, updated_at
+        RETURNING id, user_id, discord_org_id, status as "status: String", role as "role: String", created_at, updated_at
         "#,
         member_id,
-        pg_status as Option<PgMemberStatus>
+        pg_status as Option<PgMemberStatus>,
+        pg_role as Option<PgOrgRole>
     )
     .fetch_optional(pool)
     .await?;
@@ -480,7 +911,7 @@ pub async fn get_member_by_user_and_org(
 ) -> Result<Option<DbMember>, sqlx::Error> {
     let member = sqlx::query_as!(
         DbMember,
-        r#"SELECT id, user_id, discord_org_id, status as "status: String", created_at, updated_at FROM members WHERE user_id = $1 AND discord_org_id = $2"#,
+        r#"SELECT id, user_id, discord_org_id, status as "status: String", role as "role: String", created_at, updated_at FROM members WHERE user_id = $1 AND discord_org_id = $2"#,
         user_id,
         org_id
     )
@@ -491,9 +922,15 @@ pub async fn get_member_by_user_and_org(
 }
 
 // Utility functions
+/// Count an org's members who aren't banned and whose account hasn't been deleted
 pub async fn count_members_by_org(pool: &DatabasePool, org_id: Uuid) -> Result<i64, sqlx::Error> {
     let count = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM members WHERE discord_org_id = $1",
+        r#"
+        SELECT COUNT(*)
+        FROM members m
+        JOIN users u ON u.id = m.user_id
+        WHERE m.discord_org_id = $1 AND m.status <> 'banned' AND u.deleted_at IS NULL
+        "#,
         org_id
     )
     .fetch_one(pool)
@@ -520,58 +957,132 @@ pub async fn count_members_by_status(
 }
 
 // Discord token queries
+//
+// Access and refresh tokens are sealed with `TokenCipher` before they are written
+// and opened again after they are read, so callers only ever see plaintext.
+fn token_cipher() -> Result<TokenCipher, sqlx::Error> {
+    TokenCipher::from_config().map_err(|e| sqlx::Error::Configuration(e.into()))
+}
+
+struct SealedDiscordTokens {
+    key_id: String,
+    access_token: String,
+    refresh_token: Option<String>,
+    access_token_hash: String,
+}
+
+fn seal_discord_tokens(
+    cipher: &TokenCipher,
+    access_token: &str,
+    refresh_token: Option<&str>,
+) -> Result<SealedDiscordTokens, sqlx::Error> {
+    let encrypt = |field: &str, value: &str| {
+        cipher
+            .encrypt(field, value)
+            .map_err(|e| sqlx::Error::Configuration(e.into()))
+    };
+
+    Ok(SealedDiscordTokens {
+        key_id: cipher.active_key_id().to_string(),
+        access_token: encrypt("access_token", access_token)?,
+        refresh_token: refresh_token
+            .map(|token| encrypt("refresh_token", token))
+            .transpose()?,
+        access_token_hash: cipher.hash(access_token),
+    })
+}
+
+/// Replace the sealed token values of a row with their plaintext.
+/// Legacy rows without a `key_id` are still plaintext and are returned as-is.
+fn open_discord_token(
+    cipher: &TokenCipher,
+    mut token: DbDiscordToken,
+) -> Result<DbDiscordToken, sqlx::Error> {
+    if let Some(key_id) = token.key_id.as_deref() {
+        let decrypt = |field: &str, value: &str| {
+            cipher
+                .decrypt(key_id, field, value)
+                .map_err(|e| sqlx::Error::Decode(e.into()))
+        };
+
+        token.access_token = decrypt("access_token", &token.access_token)?;
+        token.refresh_token = token
+            .refresh_token
+            .as_deref()
+            .map(|value| decrypt("refresh_token", value))
+            .transpose()?;
+    }
+
+    Ok(token)
+}
+
 pub async fn create_discord_token(
     pool: &DatabasePool,
     create_token: CreateDiscordToken,
 ) -> Result<DbDiscordToken, sqlx::Error> {
+    let cipher = token_cipher()?;
+    let sealed = seal_discord_tokens(
+        &cipher,
+        &create_token.access_token,
+        create_token.refresh_token.as_deref(),
+    )?;
+
     let token = sqlx::query_as!(
         DbDiscordToken,
         r#"
-        INSERT INTO discord_tokens (user_id, access_token, refresh_token, token_type, scope, expires_at)
-        VALUES ($1, $2, $3, $4, $5, $6)
-        RETURNING id, user_id, access_token, refresh_token, token_type, scope, expires_at, created_at, updated_at
+        INSERT INTO discord_tokens (user_id, access_token, refresh_token, token_type, scope, expires_at, key_id, access_token_hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id, user_id, access_token, refresh_token, token_type, scope, expires_at, invalidated_at, key_id, access_token_hash, created_at, updated_at
         "#,
         create_token.user_id,
-        create_token.access_token,
-        create_token.refresh_token,
+        sealed.access_token,
+        sealed.refresh_token,
         create_token.token_type,
         create_token.scope,
-        create_token.expires_at
+        create_token.expires_at,
+        sealed.key_id,
+        sealed.access_token_hash
     )
     .fetch_one(pool)
     .await?;
 
-    Ok(token)
+    open_discord_token(&cipher, token)
 }
 
 pub async fn get_discord_token_by_user_id(
     pool: &DatabasePool,
     user_id: Uuid,
 ) -> Result<Option<DbDiscordToken>, sqlx::Error> {
+    let cipher = token_cipher()?;
     let token = sqlx::query_as!(
         DbDiscordToken,
-        "SELECT id, user_id, access_token, refresh_token, token_type, scope, expires_at, created_at, updated_at FROM discord_tokens WHERE user_id = $1",
+        "SELECT id, user_id, access_token, refresh_token, token_type, scope, expires_at, invalidated_at, key_id, access_token_hash, created_at, updated_at FROM discord_tokens WHERE user_id = $1",
         user_id
     )
     .fetch_optional(pool)
     .await?;
 
-    Ok(token)
+    token
+        .map(|token| open_discord_token(&cipher, token))
+        .transpose()
 }
 
 pub async fn get_discord_token_by_access_token(
     pool: &DatabasePool,
     access_token: &str,
 ) -> Result<Option<DbDiscordToken>, sqlx::Error> {
+    let cipher = token_cipher()?;
     let token = sqlx::query_as!(
         DbDiscordToken,
-        "SELECT id, user_id, access_token, refresh_token, token_type, scope, expires_at, created_at, updated_at FROM discord_tokens WHERE access_token = $1",
-        access_token
+        "SELECT id, user_id, access_token, refresh_token, token_type, scope, expires_at, invalidated_at, key_id, access_token_hash, created_at, updated_at FROM discord_tokens WHERE access_token_hash = $1",
+        cipher.hash(access_token)
     )
     .fetch_optional(pool)
     .await?;
 
-    Ok(token)
+    token
+        .map(|token| open_discord_token(&cipher, token))
+        .transpose()
 }
 
 pub async fn update_discord_token(
@@ -579,31 +1090,58 @@ pub async fn update_discord_token(
     user_id: Uuid,
     update_token: UpdateDiscordToken,
 ) -> Result<Option<DbDiscordToken>, sqlx::Error> {
+    let cipher = token_cipher()?;
+    let mut tx = pool.begin().await?;
+
+    // Both tokens are re-sealed together so the row keeps a single key_id
+    let existing = sqlx::query_as!(
+        DbDiscordToken,
+        "SELECT id, user_id, access_token, refresh_token, token_type, scope, expires_at, invalidated_at, key_id, access_token_hash, created_at, updated_at FROM discord_tokens WHERE user_id = $1 FOR UPDATE",
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(existing) = existing else {
+        return Ok(None);
+    };
+    let existing = open_discord_token(&cipher, existing)?;
+
+    let access_token = update_token.access_token.unwrap_or(existing.access_token);
+    let refresh_token = update_token.refresh_token.or(existing.refresh_token);
+    let sealed = seal_discord_tokens(&cipher, &access_token, refresh_token.as_deref())?;
+
     let token = sqlx::query_as!(
         DbDiscordToken,
         r#"
         UPDATE discord_tokens
         SET
-            access_token = COALESCE($2, access_token),
-            refresh_token = COALESCE($3, refresh_token),
-            token_type = COALESCE($4, token_type),
-            scope = COALESCE($5, scope),
-            expires_at = COALESCE($6, expires_at),
+            access_token = $2,
+            refresh_token = $3,
+            key_id = $4,
+            access_token_hash = $5,
+            token_type = COALESCE($6, token_type),
+            scope = COALESCE($7, scope),
+            expires_at = COALESCE($8, expires_at),
             updated_at = NOW()
         WHERE user_id = $1
-        RETURNING id, user_id, access_token, refresh_token, token_type, scope, expires_at, created_at, updated_at
+        RETURNING id, user_id, access_token, refresh_token, token_type, scope, expires_at, invalidated_at, key_id, access_token_hash, created_at, updated_at
         "#,
         user_id,
-        update_token.access_token,
-        update_token.refresh_token,
+        sealed.access_token,
+        sealed.refresh_token,
+        sealed.key_id,
+        sealed.access_token_hash,
         update_token.token_type,
         update_token.scope,
         update_token.expires_at
     )
-    .fetch_optional(pool)
+    .fetch_one(&mut *tx)
     .await?;
 
-    Ok(token)
+    tx.commit().await?;
+
+    open_discord_token(&cipher, token).map(Some)
 }
 
 pub async fn delete_discord_token_by_user_id(
@@ -620,10 +1158,55 @@ pub async fn delete_discord_token_by_user_id(
 pub async fn delete_discord_token_by_access_token(
     pool: &DatabasePool,
     access_token: &str,
+) -> Result<bool, sqlx::Error> {
+    let cipher = token_cipher()?;
+    let result = sqlx::query!(
+        "DELETE FROM discord_tokens WHERE access_token_hash = $1",
+        cipher.hash(access_token)
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Get usable tokens with a refresh token that expire before the given time.
+/// Each row is decrypted on its own, so a row that can't be opened (say, sealed with
+/// a retired key) comes back as an error next to its user ID instead of failing the rest.
+pub async fn get_discord_tokens_expiring_before(
+    pool: &DatabasePool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<(Uuid, Result<DbDiscordToken, sqlx::Error>)>, sqlx::Error> {
+    let cipher = token_cipher()?;
+    let tokens = sqlx::query_as!(
+        DbDiscordToken,
+        r#"
+        SELECT id, user_id, access_token, refresh_token, token_type, scope, expires_at, invalidated_at, key_id, access_token_hash, created_at, updated_at
+        FROM discord_tokens
+        WHERE refresh_token IS NOT NULL
+            AND invalidated_at IS NULL
+            AND expires_at IS NOT NULL
+            AND expires_at < $1
+        ORDER BY expires_at ASC
+        "#,
+        cutoff
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tokens
+        .into_iter()
+        .map(|token| (token.user_id, open_discord_token(&cipher, token)))
+        .collect())
+}
+
+pub async fn invalidate_discord_token(
+    pool: &DatabasePool,
+    user_id: Uuid,
 ) -> Result<bool, sqlx::Error> {
     let result = sqlx::query!(
-        "DELETE FROM discord_tokens WHERE access_token = $1",
-        access_token
+        "UPDATE discord_tokens SET invalidated_at = NOW(), updated_at = NOW() WHERE user_id = $1",
+        user_id
     )
     .execute(pool)
     .await?;
@@ -645,11 +1228,18 @@ pub async fn upsert_discord_token(
     pool: &DatabasePool,
     create_token: CreateDiscordToken,
 ) -> Result<DbDiscordToken, sqlx::Error> {
+    let cipher = token_cipher()?;
+    let sealed = seal_discord_tokens(
+        &cipher,
+        &create_token.access_token,
+        create_token.refresh_token.as_deref(),
+    )?;
+
     let token = sqlx::query_as!(
         DbDiscordToken,
         r#"
-        INSERT INTO discord_tokens (user_id, access_token, refresh_token, token_type, scope, expires_at)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        INSERT INTO discord_tokens (user_id, access_token, refresh_token, token_type, scope, expires_at, key_id, access_token_hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         ON CONFLICT (user_id)
         DO UPDATE SET
             access_token = EXCLUDED.access_token,
@@ -657,18 +1247,2407 @@ pub async fn upsert_discord_token(
             token_type = EXCLUDED.token_type,
             scope = EXCLUDED.scope,
             expires_at = EXCLUDED.expires_at,
+            key_id = EXCLUDED.key_id,
+            access_token_hash = EXCLUDED.access_token_hash,
+            invalidated_at = NULL,
             updated_at = NOW()
-        RETURNING id, user_id, access_token, refresh_token, token_type, scope, expires_at, created_at, updated_at
+        RETURNING id, user_id, access_token, refresh_token, token_type, scope, expires_at, invalidated_at, key_id, access_token_hash, created_at, updated_at
         "#,
         create_token.user_id,
-        create_token.access_token,
-        create_token.refresh_token,
+        sealed.access_token,
+        sealed.refresh_token,
         create_token.token_type,
         create_token.scope,
-        create_token.expires_at
+        create_token.expires_at,
+        sealed.key_id,
+        sealed.access_token_hash
     )
     .fetch_one(pool)
     .await?;
 
-    Ok(token)
+    open_discord_token(&cipher, token)
+}
+
+/// Outcome of re-encrypting stored Discord tokens with the active key
+#[derive(Debug)]
+pub struct TokenKeyRotation {
+    pub rotated: i64,
+    pub failed: Vec<(Uuid, String)>, // Users whose token couldn't be re-encrypted, with why
+}
+
+/// Re-encrypt every token that is not sealed with the active key, including
+/// legacy plaintext rows.
+///
+/// Safe to run next to the token refresh task: a row is only rewritten if it still
+/// holds the value that was read, so a token refreshed in the meantime (which is
+/// sealed with the active key anyway) is left alone. A row that can't be re-encrypted
+/// is reported in `failed` and doesn't stop the others.
+pub async fn rotate_discord_token_keys(
+    pool: &DatabasePool,
+) -> Result<TokenKeyRotation, sqlx::Error> {
+    let cipher = token_cipher()?;
+    let tokens = sqlx::query_as!(
+        DbDiscordToken,
+        "SELECT id, user_id, access_token, refresh_token, token_type, scope, expires_at, invalidated_at, key_id, access_token_hash, created_at, updated_at FROM discord_tokens WHERE key_id IS DISTINCT FROM $1 OR access_token_hash IS NULL",
+        cipher.active_key_id()
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut rotation = TokenKeyRotation {
+        rotated: 0,
+        failed: Vec::new(),
+    };
+    for token in tokens {
+        let (id, user_id) = (token.id, token.user_id);
+        let (old_access_token, old_key_id) = (token.access_token.clone(), token.key_id.clone());
+
+        let sealed = open_discord_token(&cipher, token).and_then(|token| {
+            seal_discord_tokens(&cipher, &token.access_token, token.refresh_token.as_deref())
+        });
+        let sealed = match sealed {
+            Ok(sealed) => sealed,
+            Err(e) => {
+                rotation.failed.push((user_id, e.to_string()));
+                continue;
+            }
+        };
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE discord_tokens
+            SET access_token = $2, refresh_token = $3, key_id = $4, access_token_hash = $5
+            WHERE id = $1 AND access_token = $6 AND key_id IS NOT DISTINCT FROM $7
+            "#,
+            id,
+            sealed.access_token,
+            sealed.refresh_token,
+            sealed.key_id,
+            sealed.access_token_hash,
+            old_access_token,
+            old_key_id
+        )
+        .execute(pool)
+        .await;
+
+        match result {
+            Ok(result) if result.rows_affected() > 0 => rotation.rotated += 1,
+            Ok(_) => {} // Refreshed or deleted since it was read
+            Err(e) => rotation.failed.push((user_id, e.to_string())),
+        }
+    }
+
+    Ok(rotation)
+}
+
+// OAuth state queries
+
+/// Save a state unless its client already holds `max_per_client` unexpired states or
+/// `max_pending` are outstanding in total, in which case `None` is returned
+pub async fn create_oauth_state(
+    pool: &DatabasePool,
+    state: &str,
+    code_challenge: &str,
+    client_ip: &str,
+    expires_at: chrono::DateTime<chrono::Utc>,
+    max_per_client: i64,
+    max_pending: i64,
+) -> Result<Option<DbOAuthState>, sqlx::Error> {
+    let oauth_state = sqlx::query_as!(
+        DbOAuthState,
+        r#"
+        INSERT INTO oauth_states (state, code_challenge, client_ip, expires_at)
+        SELECT $1::varchar, $2::varchar, $3::varchar, $4::timestamptz
+        WHERE (SELECT COUNT(*) FROM oauth_states WHERE client_ip = $3 AND expires_at > NOW()) < $5
+            AND (SELECT COUNT(*) FROM oauth_states WHERE expires_at > NOW()) < $6
+        RETURNING state, code_challenge, created_at, expires_at
+        "#,
+        state,
+        code_challenge,
+        client_ip,
+        expires_at,
+        max_per_client,
+        max_pending
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(oauth_state)
+}
+
+/// Delete a state and return it, so each state can only be used once
+pub async fn consume_oauth_state(
+    pool: &DatabasePool,
+    state: &str,
+) -> Result<Option<DbOAuthState>, sqlx::Error> {
+    let oauth_state = sqlx::query_as!(
+        DbOAuthState,
+        "DELETE FROM oauth_states WHERE state = $1 RETURNING state, code_challenge, created_at, expires_at",
+        state
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(oauth_state)
+}
+
+pub async fn cleanup_expired_oauth_states(pool: &DatabasePool) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query!("DELETE FROM oauth_states WHERE expires_at < NOW()")
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() as i64)
+}
+
+// Session queries
+pub async fn create_session(
+    pool: &DatabasePool,
+    create_session: CreateSession,
+) -> Result<DbSession, sqlx::Error> {
+    let session = sqlx::query_as!(
+        DbSession,
+        r#"
+        INSERT INTO sessions (user_id, device_label, user_agent, ip_address, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, user_id, device_label, user_agent, ip_address, created_at, last_seen_at, expires_at, revoked_at
+        "#,
+        create_session.user_id,
+        create_session.device_label,
+        create_session.user_agent,
+        create_session.ip_address,
+        create_session.expires_at
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(session)
+}
+
+pub async fn get_session_by_id(
+    pool: &DatabasePool,
+    session_id: Uuid,
+) -> Result<Option<DbSession>, sqlx::Error> {
+    let session = sqlx::query_as!(
+        DbSession,
+        "SELECT id, user_id, device_label, user_agent, ip_address, created_at, last_seen_at, expires_at, revoked_at FROM sessions WHERE id = $1",
+        session_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(session)
+}
+
+/// Get a user's sessions that are neither revoked nor expired, most recently used first
+pub async fn get_active_sessions_by_user(
+    pool: &DatabasePool,
+    user_id: Uuid,
+) -> Result<Vec<DbSession>, sqlx::Error> {
+    let sessions = sqlx::query_as!(
+        DbSession,
+        r#"
+        SELECT id, user_id, device_label, user_agent, ip_address, created_at, last_seen_at, expires_at, revoked_at
+        FROM sessions
+        WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+        ORDER BY last_seen_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(sessions)
+}
+
+/// Record that a session was used, at most once a minute to limit writes
+pub async fn touch_session(pool: &DatabasePool, session_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE sessions
+        SET last_seen_at = NOW()
+        WHERE id = $1 AND last_seen_at < NOW() - INTERVAL '1 minute'
+        "#,
+        session_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn revoke_session(pool: &DatabasePool, session_id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        "UPDATE sessions SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL",
+        session_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Revoke one of a user's sessions; returns false if it isn't theirs or is already revoked
+pub async fn revoke_user_session(
+    pool: &DatabasePool,
+    user_id: Uuid,
+    session_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        "UPDATE sessions SET revoked_at = NOW() WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+        session_id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Revoke all of a user's sessions, optionally keeping one (e.g. the caller's own)
+pub async fn revoke_all_user_sessions(
+    pool: &DatabasePool,
+    user_id: Uuid,
+    except_session_id: Option<Uuid>,
+) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE sessions
+        SET revoked_at = NOW()
+        WHERE user_id = $1 AND revoked_at IS NULL AND id IS DISTINCT FROM $2
+        "#,
+        user_id,
+        except_session_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() as i64)
+}
+
+// API key queries
+pub async fn create_api_key(
+    pool: &DatabasePool,
+    create_key: CreateApiKey,
+) -> Result<DbApiKey, sqlx::Error> {
+    let key = sqlx::query_as!(
+        DbApiKey,
+        r#"
+        INSERT INTO api_keys (discord_org_id, created_by, name, key_prefix, key_hash, scopes, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, discord_org_id, created_by, name, key_prefix, key_hash, scopes, expires_at, last_used_at, revoked_at, created_at
+        "#,
+        create_key.discord_org_id,
+        create_key.created_by,
+        create_key.name,
+        create_key.key_prefix,
+        create_key.key_hash,
+        &create_key.scopes,
+        create_key.expires_at
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(key)
+}
+
+pub async fn get_api_key_by_hash(
+    pool: &DatabasePool,
+    key_hash: &str,
+) -> Result<Option<DbApiKey>, sqlx::Error> {
+    let key = sqlx::query_as!(
+        DbApiKey,
+        r#"
+        SELECT id, discord_org_id, created_by, name, key_prefix, key_hash, scopes, expires_at, last_used_at, revoked_at, created_at
+        FROM api_keys
+        WHERE key_hash = $1
+          AND discord_org_id IN (SELECT id FROM discord_orgs WHERE deleted_at IS NULL)
+        "#,
+        key_hash
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(key)
+}
+
+pub async fn get_api_keys_by_org(
+    pool: &DatabasePool,
+    org_id: Uuid,
+) -> Result<Vec<DbApiKey>, sqlx::Error> {
+    let keys = sqlx::query_as!(
+        DbApiKey,
+        "SELECT id, discord_org_id, created_by, name, key_prefix, key_hash, scopes, expires_at, last_used_at, revoked_at, created_at FROM api_keys WHERE discord_org_id = $1 ORDER BY created_at DESC",
+        org_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(keys)
+}
+
+/// Record that a key was used, at most once a minute to limit writes
+pub async fn touch_api_key(pool: &DatabasePool, key_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE api_keys
+        SET last_used_at = NOW()
+        WHERE id = $1 AND (last_used_at IS NULL OR last_used_at < NOW() - INTERVAL '1 minute')
+        "#,
+        key_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Revoke one of an org's keys; returns false if it isn't theirs or is already revoked
+pub async fn revoke_api_key(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    key_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        "UPDATE api_keys SET revoked_at = NOW() WHERE id = $1 AND discord_org_id = $2 AND revoked_at IS NULL",
+        key_id,
+        org_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// Org invite queries
+pub async fn create_org_invite(
+    pool: &DatabasePool,
+    create_invite: CreateOrgInvite,
+) -> Result<DbOrgInvite, sqlx::Error> {
+    let pg_status: PgMemberStatus = create_invite.initial_status.into();
+
+    let invite = sqlx::query_as!(
+        DbOrgInvite,
+        r#"
+        INSERT INTO org_invites (discord_org_id, created_by, code, initial_status, max_uses, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, discord_org_id, created_by, code, initial_status as "initial_status: String", max_uses, uses, expires_at, revoked_at, created_at
+        "#,
+        create_invite.discord_org_id,
+        create_invite.created_by,
+        create_invite.code,
+        pg_status as PgMemberStatus,
+        create_invite.max_uses,
+        create_invite.expires_at
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(invite)
+}
+
+pub async fn get_org_invites_by_org(
+    pool: &DatabasePool,
+    org_id: Uuid,
+) -> Result<Vec<DbOrgInvite>, sqlx::Error> {
+    let invites = sqlx::query_as!(
+        DbOrgInvite,
+        r#"
+        SELECT id, discord_org_id, created_by, code, initial_status as "initial_status: String", max_uses, uses, expires_at, revoked_at, created_at
+        FROM org_invites
+        WHERE discord_org_id = $1
+        ORDER BY created_at DESC
+        "#,
+        org_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(invites)
+}
+
+/// Revoke one of an org's invites; returns false if it isn't theirs or is already revoked
+pub async fn revoke_org_invite(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    invite_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        "UPDATE org_invites SET revoked_at = NOW() WHERE id = $1 AND discord_org_id = $2 AND revoked_at IS NULL",
+        invite_id,
+        org_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Get the users who joined through one of an org's invites, newest first
+pub async fn get_invite_redemptions(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    invite_id: Uuid,
+) -> Result<Vec<DbInviteRedemptionWithUser>, sqlx::Error> {
+    let redemptions = sqlx::query_as!(
+        DbInviteRedemptionWithUser,
+        r#"
+        SELECT
+            r.id,
+            r.invite_id,
+            r.member_id,
+            r.redeemed_at,
+
+            u.id as user_id,
+            u.discord_id as user_discord_id,
+            u.display_name as user_display_name,
+            u.avatar_url as user_avatar_url,
+            u.bio as user_bio,
+            u.created_at as user_created_at,
+            u.updated_at as user_updated_at
+        FROM invite_redemptions r
+        JOIN org_invites i ON r.invite_id = i.id
+        JOIN users u ON r.user_id = u.id
+        WHERE r.invite_id = $1 AND i.discord_org_id = $2
+        ORDER BY r.redeemed_at DESC
+        "#,
+        invite_id,
+        org_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(redemptions)
+}
+
+/// Outcome of redeeming an invite code
+#[derive(Debug)]
+pub enum InviteRedemption {
+    Redeemed(DbMember),
+    NotFound,
+    Expired,
+    Exhausted,
+    AlreadyMember,
+    Banned,
+}
+
+/// Redeem an invite code: create the membership, count the use and record who
+/// redeemed it, all in one transaction
+pub async fn redeem_org_invite(
+    pool: &DatabasePool,
+    code: &str,
+    user_id: Uuid,
+) -> Result<InviteRedemption, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    // Lock the invite so concurrent redemptions can't exceed max_uses
+    let invite = sqlx::query_as!(
+        DbOrgInvite,
+        r#"
+        SELECT id, discord_org_id, created_by, code, initial_status as "initial_status: String", max_uses, uses, expires_at, revoked_at, created_at
+        FROM org_invites
+        WHERE code = $1
+          AND discord_org_id IN (SELECT id FROM discord_orgs WHERE deleted_at IS NULL)
+        FOR UPDATE
+        "#,
+        code
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(invite) = invite.filter(|invite| invite.revoked_at.is_none()) else {
+        return Ok(InviteRedemption::NotFound);
+    };
+    if invite
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= chrono::Utc::now())
+    {
+        return Ok(InviteRedemption::Expired);
+    }
+    if invite
+        .max_uses
+        .is_some_and(|max_uses| invite.uses >= max_uses)
+    {
+        return Ok(InviteRedemption::Exhausted);
+    }
+
+    let member = sqlx::query_as!(
+        DbMember,
+        r#"
+        INSERT INTO members (user_id, discord_org_id, status)
+        VALUES ($1, $2, $3::text::member_status)
+        ON CONFLICT (user_id, discord_org_id) DO NOTHING
+        RETURNING id, user_id, discord_org_id, status as "status: String", role as "role: String", created_at, updated_at
+        "#,
+        user_id,
+        invite.discord_org_id,
+        invite.initial_status
+    )
+    .fetch_optional(&mut *tx)
+    .await;
+
+    let member = match member {
+        Err(e) if is_member_banned_error(&e) => return Ok(InviteRedemption::Banned),
+        result => result?,
+    };
+
+    let Some(member) = member else {
+        return Ok(InviteRedemption::AlreadyMember);
+    };
+
+    let reason = format!("Joined with invite {}", invite.code);
+    record_status_change(
+        &mut tx,
+        member.id,
+        None,
+        &member.status,
+        Some(user_id),
+        Some(&reason),
+    )
+    .await?;
+
+    sqlx::query!(
+        "UPDATE org_invites SET uses = uses + 1 WHERE id = $1",
+        invite.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Users who left and rejoin through the same code keep a single redemption row
+    sqlx::query!(
+        r#"
+        INSERT INTO invite_redemptions (invite_id, user_id, member_id)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (invite_id, user_id) DO UPDATE SET member_id = EXCLUDED.member_id, redeemed_at = NOW()
+        "#,
+        invite.id,
+        user_id,
+        member.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(InviteRedemption::Redeemed(member))
+}
+
+// Member ban queries
+/// Ban a member: supersede any open ban, record the new one and mark the membership
+/// banned, in one transaction
+pub async fn ban_member(
+    pool: &DatabasePool,
+    create_ban: CreateMemberBan,
+) -> Result<DbMemberBan, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE member_bans
+        SET lifted_at = NOW(), lifted_by = $3
+        WHERE discord_org_id = $1 AND user_id = $2 AND lifted_at IS NULL
+        "#,
+        create_ban.discord_org_id,
+        create_ban.user_id,
+        create_ban.banned_by
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let ban = sqlx::query_as!(
+        DbMemberBan,
+        r#"
+        INSERT INTO member_bans (discord_org_id, user_id, banned_by, reason, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, discord_org_id, user_id, banned_by, reason, banned_at, expires_at, lifted_at, lifted_by
+        "#,
+        create_ban.discord_org_id,
+        create_ban.user_id,
+        create_ban.banned_by,
+        create_ban.reason,
+        create_ban.expires_at
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let member = sqlx::query!(
+        r#"
+        SELECT id, status as "status: String" FROM members
+        WHERE discord_org_id = $1 AND user_id = $2
+        FOR UPDATE
+        "#,
+        create_ban.discord_org_id,
+        create_ban.user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    // A replacement ban leaves an already banned member's status untouched
+    if let Some(member) = member.filter(|member| member.status != "banned") {
+        sqlx::query!(
+            "UPDATE members SET status = 'banned' WHERE id = $1",
+            member.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        record_status_change(
+            &mut tx,
+            member.id,
+            Some(&member.status),
+            "banned",
+            create_ban.banned_by,
+            create_ban.reason.as_deref(),
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(ban)
+}
+
+/// Lift a user's open ban in an org and restore their membership to spectating;
+/// returns false if they had no open ban
+pub async fn unban_member(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    user_id: Uuid,
+    lifted_by: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let result = sqlx::query!(
+        r#"
+        UPDATE member_bans
+        SET lifted_at = NOW(), lifted_by = $3
+        WHERE discord_org_id = $1 AND user_id = $2 AND lifted_at IS NULL
+        "#,
+        org_id,
+        user_id,
+        lifted_by
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let restored = sqlx::query_scalar!(
+        r#"
+        UPDATE members SET status = 'spectating'
+        WHERE discord_org_id = $1 AND user_id = $2 AND status = 'banned'
+        RETURNING id
+        "#,
+        org_id,
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if let Some(member_id) = restored {
+        record_status_change(
+            &mut tx,
+            member_id,
+            Some("banned"),
+            "spectating",
+            Some(lifted_by),
+            Some("Unbanned"),
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Get an org's open bans, newest first
+pub async fn get_open_bans_by_org(
+    pool: &DatabasePool,
+    org_id: Uuid,
+) -> Result<Vec<DbMemberBan>, sqlx::Error> {
+    let bans = sqlx::query_as!(
+        DbMemberBan,
+        r#"
+        SELECT id, discord_org_id, user_id, banned_by, reason, banned_at, expires_at, lifted_at, lifted_by
+        FROM member_bans
+        WHERE discord_org_id = $1 AND lifted_at IS NULL
+        ORDER BY banned_at DESC
+        "#,
+        org_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(bans)
+}
+
+/// Lift bans whose expiry has passed and restore those members to spectating;
+/// returns the number of bans lifted
+pub async fn lift_expired_bans(pool: &DatabasePool) -> Result<i64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let lifted = sqlx::query!(
+        r#"
+        UPDATE member_bans
+        SET lifted_at = expires_at
+        WHERE lifted_at IS NULL AND expires_at <= NOW()
+        RETURNING discord_org_id, user_id
+        "#
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for ban in &lifted {
+        let restored = sqlx::query_scalar!(
+            r#"
+            UPDATE members SET status = 'spectating'
+            WHERE discord_org_id = $1 AND user_id = $2 AND status = 'banned'
+            RETURNING id
+            "#,
+            ban.discord_org_id,
+            ban.user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(member_id) = restored {
+            record_status_change(
+                &mut tx,
+                member_id,
+                Some("banned"),
+                "spectating",
+                None,
+                Some("Ban expired"),
+            )
+            .await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    Ok(lifted.len() as i64)
+}
+
+// Join request queries
+/// Create a pending join request; fails with a duplicate key error while the user
+/// already has one pending in the org
+pub async fn create_join_request(
+    pool: &DatabasePool,
+    create_request: CreateJoinRequest,
+) -> Result<DbJoinRequest, sqlx::Error> {
+    let request = sqlx::query_as!(
+        DbJoinRequest,
+        r#"
+        INSERT INTO join_requests (discord_org_id, user_id, message)
+        VALUES ($1, $2, $3)
+        RETURNING id, discord_org_id, user_id, message, status as "status: String", decided_by, decision_reason, member_id, created_at, decided_at
+        "#,
+        create_request.discord_org_id,
+        create_request.user_id,
+        create_request.message
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(request)
+}
+
+pub async fn get_join_request_by_id(
+    pool: &DatabasePool,
+    request_id: Uuid,
+) -> Result<Option<DbJoinRequest>, sqlx::Error> {
+    let request = sqlx::query_as!(
+        DbJoinRequest,
+        r#"
+        SELECT id, discord_org_id, user_id, message, status as "status: String", decided_by, decision_reason, member_id, created_at, decided_at
+        FROM join_requests
+        WHERE id = $1
+        "#,
+        request_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(request)
+}
+
+/// Get an org's pending join requests, oldest first
+pub async fn get_pending_join_requests_by_org(
+    pool: &DatabasePool,
+    org_id: Uuid,
+) -> Result<Vec<DbJoinRequest>, sqlx::Error> {
+    let requests = sqlx::query_as!(
+        DbJoinRequest,
+        r#"
+        SELECT id, discord_org_id, user_id, message, status as "status: String", decided_by, decision_reason, member_id, created_at, decided_at
+        FROM join_requests
+        WHERE discord_org_id = $1 AND status = 'pending'
+        ORDER BY created_at
+        "#,
+        org_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(requests)
+}
+
+/// Get every join request a user has made, newest first
+pub async fn get_join_requests_by_user(
+    pool: &DatabasePool,
+    user_id: Uuid,
+) -> Result<Vec<DbJoinRequest>, sqlx::Error> {
+    let requests = sqlx::query_as!(
+        DbJoinRequest,
+        r#"
+        SELECT id, discord_org_id, user_id, message, status as "status: String", decided_by, decision_reason, member_id, created_at, decided_at
+        FROM join_requests
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(requests)
+}
+
+/// Approve or reject a pending join request; returns None if it is no longer pending
+pub async fn decide_join_request(
+    pool: &DatabasePool,
+    request_id: Uuid,
+    status: JoinRequestStatus,
+    decided_by: Uuid,
+    reason: Option<&str>,
+    member_id: Option<Uuid>,
+) -> Result<Option<DbJoinRequest>, sqlx::Error> {
+    let request = sqlx::query_as!(
+        DbJoinRequest,
+        r#"
+        UPDATE join_requests
+        SET status = $2::text::join_request_status, decided_by = $3, decision_reason = $4, member_id = $5, decided_at = NOW()
+        WHERE id = $1 AND status = 'pending'
+        RETURNING id, discord_org_id, user_id, message, status as "status: String", decided_by, decision_reason, member_id, created_at, decided_at
+        "#,
+        request_id,
+        String::from(status),
+        decided_by,
+        reason,
+        member_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(request)
+}
+
+/// Approve a pending join request, adding the applicant as a `Spectating` member in the
+/// same transaction; returns None if the request is no longer pending
+pub async fn approve_join_request(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    request_id: Uuid,
+    decided_by: Uuid,
+) -> Result<Option<DbMember>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let user_id = sqlx::query_scalar!(
+        r#"
+        SELECT user_id FROM join_requests
+        WHERE id = $1 AND discord_org_id = $2 AND status = 'pending'
+        FOR UPDATE
+        "#,
+        request_id,
+        org_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(user_id) = user_id else {
+        return Ok(None);
+    };
+
+    let create_member = CreateMember {
+        user_id,
+        discord_org_id: org_id,
+        status: MemberStatus::Spectating,
+        role: OrgRole::Member,
+    };
+    let member = insert_member(&mut tx, create_member).await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE join_requests
+        SET status = 'approved', decided_by = $2, member_id = $3, decided_at = NOW()
+        WHERE id = $1
+        "#,
+        request_id,
+        decided_by,
+        member.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Some(member))
+}
+
+/// Withdraw one of a user's pending join requests; returns false if it isn't theirs
+/// or is no longer pending
+pub async fn withdraw_join_request(
+    pool: &DatabasePool,
+    request_id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE join_requests
+        SET status = 'withdrawn', decided_at = NOW()
+        WHERE id = $1 AND user_id = $2 AND status = 'pending'
+        "#,
+        request_id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Whether the user has an active ban in the org
+pub async fn is_user_banned(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let banned = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM member_bans
+            WHERE discord_org_id = $1
+              AND user_id = $2
+              AND lifted_at IS NULL
+              AND (expires_at IS NULL OR expires_at > NOW())
+        ) as "banned!"
+        "#,
+        org_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(banned)
+}
+
+// Purge queries
+/// Hard-delete users and orgs soft-deleted before `cutoff`; returns the number of users
+/// and orgs removed or anonymized. Their memberships, tokens and other dependent rows go
+/// with them through `ON DELETE CASCADE`.
+///
+/// Users who took part in matches of a remaining org are anonymized into tombstones
+/// instead, so other players' match history and ratings stay as they were.
+pub async fn purge_deleted(
+    pool: &DatabasePool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+) -> Result<i64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let orgs = sqlx::query!("DELETE FROM discord_orgs WHERE deleted_at < $1", cutoff)
+        .execute(&mut *tx)
+        .await?;
+
+    // Also picks up tombstones whose matches have since gone with their org
+    let users = sqlx::query!(
+        r#"
+        DELETE FROM users
+        WHERE deleted_at < $1
+            AND NOT EXISTS (SELECT 1 FROM match_participants mp WHERE mp.user_id = users.id)
+        "#,
+        cutoff
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let tombstones = sqlx::query_scalar!(
+        r#"
+        UPDATE users
+        SET discord_id = 'purged:' || id, display_name = 'Deleted user', avatar_url = NULL,
+            bio = NULL, purged_at = NOW()
+        WHERE deleted_at < $1 AND purged_at IS NULL
+        RETURNING id
+        "#,
+        cutoff
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    // Keep only what match history needs: ratings, standings and match participation
+    sqlx::query!("DELETE FROM sessions WHERE user_id = ANY($1)", &tombstones)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query!(
+        "DELETE FROM discord_tokens WHERE user_id = ANY($1)",
+        &tombstones
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!("DELETE FROM members WHERE user_id = ANY($1)", &tombstones)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok((orgs.rows_affected() + users.rows_affected()) as i64 + tombstones.len() as i64)
+}
+
+// Ownership transfer queries
+/// Nominate a new owner, cancelling any transfer the org still has pending
+pub async fn create_ownership_transfer(
+    pool: &DatabasePool,
+    create_transfer: CreateOwnershipTransfer,
+) -> Result<DbOwnershipTransfer, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE ownership_transfers SET status = 'cancelled', resolved_at = NOW()
+        WHERE discord_org_id = $1 AND status = 'pending'
+        "#,
+        create_transfer.discord_org_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let transfer = sqlx::query_as!(
+        DbOwnershipTransfer,
+        r#"
+        INSERT INTO ownership_transfers (discord_org_id, from_user_id, to_user_id, initiated_by, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, discord_org_id, from_user_id, to_user_id, initiated_by, status as "status: String", expires_at, created_at, resolved_at
+        "#,
+        create_transfer.discord_org_id,
+        create_transfer.from_user_id,
+        create_transfer.to_user_id,
+        create_transfer.initiated_by,
+        create_transfer.expires_at
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(transfer)
+}
+
+/// Get every ownership transfer of an org, newest first
+pub async fn get_ownership_transfers_by_org(
+    pool: &DatabasePool,
+    org_id: Uuid,
+) -> Result<Vec<DbOwnershipTransfer>, sqlx::Error> {
+    let transfers = sqlx::query_as!(
+        DbOwnershipTransfer,
+        r#"
+        SELECT id, discord_org_id, from_user_id, to_user_id, initiated_by, status as "status: String", expires_at, created_at, resolved_at
+        FROM ownership_transfers
+        WHERE discord_org_id = $1
+        ORDER BY created_at DESC
+        "#,
+        org_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(transfers)
+}
+
+/// Get the pending, unexpired transfers nominating a user
+pub async fn get_pending_ownership_transfers_for_user(
+    pool: &DatabasePool,
+    user_id: Uuid,
+) -> Result<Vec<DbOwnershipTransfer>, sqlx::Error> {
+    let transfers = sqlx::query_as!(
+        DbOwnershipTransfer,
+        r#"
+        SELECT id, discord_org_id, from_user_id, to_user_id, initiated_by, status as "status: String", expires_at, created_at, resolved_at
+        FROM ownership_transfers
+        WHERE to_user_id = $1 AND status = 'pending' AND expires_at > NOW()
+        ORDER BY created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(transfers)
+}
+
+/// Cancel an org's pending transfer; returns false if it has none
+pub async fn cancel_ownership_transfer(
+    pool: &DatabasePool,
+    org_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE ownership_transfers SET status = 'cancelled', resolved_at = NOW()
+        WHERE discord_org_id = $1 AND status = 'pending'
+        "#,
+        org_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Decline a pending transfer nominating `user_id`; returns false if there is none
+pub async fn decline_ownership_transfer(
+    pool: &DatabasePool,
+    transfer_id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE ownership_transfers SET status = 'declined', resolved_at = NOW()
+        WHERE id = $1 AND to_user_id = $2 AND status = 'pending'
+        "#,
+        transfer_id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Outcome of accepting an ownership transfer
+#[derive(Debug)]
+pub enum OwnershipTransferAcceptance {
+    Accepted(DbDiscordOrg),
+    NotFound,
+    Expired,
+    OwnerChanged, // The org changed hands since the nomination
+    NotMember,    // The nominee left or was banned since the nomination
+}
+
+/// Accept a transfer nominating `user_id`: the nominee becomes owner and the old owner
+/// is demoted to admin, in one transaction
+pub async fn accept_ownership_transfer(
+    pool: &DatabasePool,
+    transfer_id: Uuid,
+    user_id: Uuid,
+) -> Result<OwnershipTransferAcceptance, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let transfer = sqlx::query_as!(
+        DbOwnershipTransfer,
+        r#"
+        SELECT id, discord_org_id, from_user_id, to_user_id, initiated_by, status as "status: String", expires_at, created_at, resolved_at
+        FROM ownership_transfers
+        WHERE id = $1 AND to_user_id = $2 AND status = 'pending'
+        FOR UPDATE
+        "#,
+        transfer_id,
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(transfer) = transfer else {
+        return Ok(OwnershipTransferAcceptance::NotFound);
+    };
+    if transfer.expires_at <= chrono::Utc::now() {
+        return Ok(OwnershipTransferAcceptance::Expired);
+    }
+
+    // Lock the org so concurrent transfers can't interleave
+    let owner_id = sqlx::query_scalar!(
+        "SELECT owner_id FROM discord_orgs WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+        transfer.discord_org_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(owner_id) = owner_id else {
+        return Ok(OwnershipTransferAcceptance::NotFound);
+    };
+    if Some(owner_id) != transfer.from_user_id {
+        return Ok(OwnershipTransferAcceptance::OwnerChanged);
+    }
+
+    // Demote the old owner first: an org can only hold one owner membership
+    sqlx::query!(
+        "UPDATE members SET role = 'admin' WHERE discord_org_id = $1 AND user_id = $2",
+        transfer.discord_org_id,
+        owner_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let promoted = sqlx::query!(
+        r#"
+        UPDATE members SET role = 'owner'
+        WHERE discord_org_id = $1 AND user_id = $2 AND status <> 'banned'
+        "#,
+        transfer.discord_org_id,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if promoted.rows_affected() == 0 {
+        return Ok(OwnershipTransferAcceptance::NotMember);
+    }
+
+    let org = sqlx::query_as!(
+        DbDiscordOrg,
+        r#"
+        UPDATE discord_orgs SET owner_id = $2, updated_at = NOW()
+        WHERE id = $1
+        RETURNING id, owner_id, name, avatar_url, description, guild_id, requires_approval, listed, tags, created_at, updated_at
+        "#,
+        transfer.discord_org_id,
+        user_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE ownership_transfers SET status = 'accepted', resolved_at = NOW() WHERE id = $1",
+        transfer.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(OwnershipTransferAcceptance::Accepted(org))
+}
+
+// Match queries
+
+/// Insert a match's results; `confirmed_by` (the reporter) has already confirmed theirs
+async fn insert_match_participants(
+    conn: &mut sqlx::PgConnection,
+    match_id: Uuid,
+    participants: &[CreateMatchParticipant],
+    confirmed_by: Option<Uuid>,
+) -> Result<Vec<DbMatchParticipant>, sqlx::Error> {
+    let mut inserted = Vec::with_capacity(participants.len());
+
+    for participant in participants {
+        let confirmation = if Some(participant.user_id) == confirmed_by {
+            MatchConfirmation::Confirmed
+        } else {
+            MatchConfirmation::Pending
+        };
+
+        let row = sqlx::query_as!(
+            DbMatchParticipant,
+            r#"
+            INSERT INTO match_participants (match_id, user_id, placement, score, confirmation, responded_at)
+            VALUES ($1, $2, $3, $4, $5::text::match_confirmation, CASE WHEN $5::text = 'confirmed' THEN NOW() END)
+            RETURNING id, match_id, user_id, placement, score, confirmation as "confirmation: String", dispute_reason, responded_at, created_at
+            "#,
+            match_id,
+            participant.user_id,
+            participant.placement,
+            participant.score,
+            String::from(confirmation)
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        inserted.push(row);
+    }
+
+    inserted.sort_by_key(|participant| participant.placement);
+
+    Ok(inserted)
+}
+
+/// Record a pending match and its results in one transaction
+pub async fn create_match(
+    pool: &DatabasePool,
+    create_match: CreateMatch,
+) -> Result<(DbMatch, Vec<DbMatchParticipant>), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let db_match = sqlx::query_as!(
+        DbMatch,
+        r#"
+        INSERT INTO matches (discord_org_id, game_type, reported_by, started_at, ended_at, confirm_deadline)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, discord_org_id, game_type, reported_by, started_at, ended_at, status as "status: String", confirm_deadline, finalized_at, resolved_by, resolution_note, created_at, updated_at
+        "#,
+        create_match.discord_org_id,
+        create_match.game_type,
+        create_match.reported_by,
+        create_match.started_at,
+        create_match.ended_at,
+        create_match.confirm_deadline
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let participants = insert_match_participants(
+        &mut tx,
+        db_match.id,
+        &create_match.participants,
+        create_match.reported_by,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((db_match, participants))
+}
+
+pub async fn get_match_by_id(
+    pool: &DatabasePool,
+    match_id: Uuid,
+) -> Result<Option<DbMatch>, sqlx::Error> {
+    let db_match = sqlx::query_as!(
+        DbMatch,
+        r#"
+        SELECT id, discord_org_id, game_type, reported_by, started_at, ended_at, status as "status: String", confirm_deadline, finalized_at, resolved_by, resolution_note, created_at, updated_at
+        FROM matches
+        WHERE id = $1
+        "#,
+        match_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(db_match)
+}
+
+/// Get the results of several matches at once, each match's ordered by placement
+pub async fn get_match_participants(
+    pool: &DatabasePool,
+    match_ids: &[Uuid],
+) -> Result<Vec<DbMatchParticipant>, sqlx::Error> {
+    let participants = sqlx::query_as!(
+        DbMatchParticipant,
+        r#"
+        SELECT id, match_id, user_id, placement, score, confirmation as "confirmation: String", dispute_reason, responded_at, created_at
+        FROM match_participants
+        WHERE match_id = ANY($1)
+        ORDER BY match_id, placement, created_at
+        "#,
+        match_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(participants)
+}
+
+/// Get an org's matches, most recently ended first, optionally for one game type or
+/// status
+pub async fn get_matches_by_org(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    game_type: Option<&str>,
+    status: Option<MatchStatus>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<DbMatch>, sqlx::Error> {
+    let matches = sqlx::query_as!(
+        DbMatch,
+        r#"
+        SELECT id, discord_org_id, game_type, reported_by, started_at, ended_at, status as "status: String", confirm_deadline, finalized_at, resolved_by, resolution_note, created_at, updated_at
+        FROM matches
+        WHERE discord_org_id = $1
+            AND ($2::text IS NULL OR game_type = $2)
+            AND ($3::text IS NULL OR status = $3::text::match_status)
+        ORDER BY ended_at DESC
+        LIMIT $4 OFFSET $5
+        "#,
+        org_id,
+        game_type,
+        status.map(String::from),
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(matches)
+}
+
+/// Update a match that isn't settled yet; when `participants` is set, every result is
+/// replaced and the match goes back to pending until `confirm_deadline`
+pub async fn update_match(
+    pool: &DatabasePool,
+    match_id: Uuid,
+    update_match: UpdateMatch,
+    confirm_deadline: chrono::DateTime<chrono::Utc>,
+) -> Result<Option<(DbMatch, Vec<DbMatchParticipant>)>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let db_match = sqlx::query_as!(
+        DbMatch,
+        r#"
+        UPDATE matches
+        SET
+            game_type = COALESCE($2, game_type),
+            started_at = COALESCE($3, started_at),
+            ended_at = COALESCE($4, ended_at),
+            updated_at = NOW()
+        WHERE id = $1 AND status IN ('pending', 'disputed')
+        RETURNING id, discord_org_id, game_type, reported_by, started_at, ended_at, status as "status: String", confirm_deadline, finalized_at, resolved_by, resolution_note, created_at, updated_at
+        "#,
+        match_id,
+        update_match.game_type,
+        update_match.started_at,
+        update_match.ended_at
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(mut db_match) = db_match else {
+        return Ok(None);
+    };
+
+    let participants = match update_match.participants {
+        Some(participants) => {
+            sqlx::query!(
+                "DELETE FROM match_participants WHERE match_id = $1",
+                match_id
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            // New results reopen confirmation, clearing any dispute
+            db_match = sqlx::query_as!(
+                DbMatch,
+                r#"
+                UPDATE matches SET status = 'pending', confirm_deadline = $2
+                WHERE id = $1
+                RETURNING id, discord_org_id, game_type, reported_by, started_at, ended_at, status as "status: String", confirm_deadline, finalized_at, resolved_by, resolution_note, created_at, updated_at
+                "#,
+                match_id,
+                confirm_deadline
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            insert_match_participants(&mut tx, match_id, &participants, None).await?
+        }
+        None => sqlx::query_as!(
+            DbMatchParticipant,
+            r#"
+            SELECT id, match_id, user_id, placement, score, confirmation as "confirmation: String", dispute_reason, responded_at, created_at
+            FROM match_participants
+            WHERE match_id = $1
+            ORDER BY placement, created_at
+            "#,
+            match_id
+        )
+        .fetch_all(&mut *tx)
+        .await?,
+    };
+
+    tx.commit().await?;
+
+    Ok(Some((db_match, participants)))
+}
+
+/// Delete a match; deleting a finalized match replays the ratings it affected
+pub async fn delete_match(pool: &DatabasePool, match_id: Uuid) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let deleted = sqlx::query!(
+        r#"
+        DELETE FROM matches WHERE id = $1
+        RETURNING discord_org_id, game_type, status = 'finalized' as "was_finalized!"
+        "#,
+        match_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(deleted) = deleted else {
+        return Ok(false);
+    };
+    if deleted.was_finalized {
+        replay_ratings(&mut tx, deleted.discord_org_id, &deleted.game_type).await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// Mark a locked match as finalized and update its participants' ratings, so it
+/// counts towards stats and the leaderboard
+async fn finalize_match(
+    conn: &mut sqlx::PgConnection,
+    match_id: Uuid,
+) -> Result<DbMatch, sqlx::Error> {
+    let db_match = sqlx::query_as!(
+        DbMatch,
+        r#"
+        UPDATE matches SET status = 'finalized', finalized_at = NOW()
+        WHERE id = $1
+        RETURNING id, discord_org_id, game_type, reported_by, started_at, ended_at, status as "status: String", confirm_deadline, finalized_at, resolved_by, resolution_note, created_at, updated_at
+        "#,
+        match_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    // Ratings follow the order matches ended in, so a match finalized after a later one
+    // (or after a season reset that came later) is rated by replaying the game type
+    // rather than on top of the current ratings
+    lock_ratings(conn, db_match.discord_org_id, &db_match.game_type).await?;
+    let rated_later = sqlx::query_scalar!(
+        r#"
+        SELECT (
+            EXISTS (
+                SELECT 1 FROM matches
+                WHERE discord_org_id = $1 AND game_type = $2 AND status = 'finalized' AND id <> $3
+                  AND (ended_at, finalized_at, id) > ($4, $5, $3)
+            )
+            OR EXISTS (
+                SELECT 1 FROM seasons
+                WHERE discord_org_id = $1 AND archived_at IS NOT NULL AND ends_at > $4
+            )
+        ) as "exists!"
+        "#,
+        db_match.discord_org_id,
+        db_match.game_type,
+        db_match.id,
+        db_match.ended_at,
+        db_match.finalized_at
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if rated_later {
+        replay_ratings(conn, db_match.discord_org_id, &db_match.game_type).await?;
+    } else {
+        apply_match_ratings(conn, &db_match).await?;
+    }
+
+    Ok(db_match)
+}
+
+/// Outcome of a participant confirming or disputing a reported result
+#[derive(Debug)]
+pub enum MatchResponse {
+    Recorded(Box<DbMatch>), // The match as it stands after the response
+    NotFound,
+    NotParticipant,
+    NotPending,   // Already disputed, finalized or voided
+    WindowClosed, // The confirmation deadline passed
+}
+
+/// Record a participant's confirmation or dispute of a pending match. A dispute moves
+/// the match to the org's dispute queue; the last confirmation finalizes it.
+pub async fn respond_to_match(
+    pool: &DatabasePool,
+    match_id: Uuid,
+    user_id: Uuid,
+    confirmation: MatchConfirmation,
+    dispute_reason: Option<&str>,
+) -> Result<MatchResponse, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let db_match = sqlx::query_as!(
+        DbMatch,
+        r#"
+        SELECT id, discord_org_id, game_type, reported_by, started_at, ended_at, status as "status: String", confirm_deadline, finalized_at, resolved_by, resolution_note, created_at, updated_at
+        FROM matches
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        match_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(db_match) = db_match else {
+        return Ok(MatchResponse::NotFound);
+    };
+    if db_match.status != String::from(MatchStatus::Pending) {
+        return Ok(MatchResponse::NotPending);
+    }
+    if db_match.confirm_deadline <= chrono::Utc::now() {
+        return Ok(MatchResponse::WindowClosed);
+    }
+
+    let responded = sqlx::query!(
+        r#"
+        UPDATE match_participants
+        SET confirmation = $3::text::match_confirmation, dispute_reason = $4, responded_at = NOW()
+        WHERE match_id = $1 AND user_id = $2
+        "#,
+        match_id,
+        user_id,
+        String::from(confirmation),
+        dispute_reason
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if responded.rows_affected() == 0 {
+        return Ok(MatchResponse::NotParticipant);
+    }
+
+    let db_match = if confirmation == MatchConfirmation::Disputed {
+        sqlx::query_as!(
+            DbMatch,
+            r#"
+            UPDATE matches SET status = 'disputed', updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, discord_org_id, game_type, reported_by, started_at, ended_at, status as "status: String", confirm_deadline, finalized_at, resolved_by, resolution_note, created_at, updated_at
+            "#,
+            match_id
+        )
+        .fetch_one(&mut *tx)
+        .await?
+    } else {
+        let unconfirmed = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!" FROM match_participants
+            WHERE match_id = $1 AND confirmation <> 'confirmed'
+            "#,
+            match_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if unconfirmed == 0 {
+            finalize_match(&mut tx, match_id).await?
+        } else {
+            db_match
+        }
+    };
+
+    tx.commit().await?;
+
+    Ok(MatchResponse::Recorded(Box::new(db_match)))
+}
+
+/// Finalize pending matches whose confirmation window passed without a dispute
+pub async fn finalize_expired_matches(pool: &DatabasePool) -> Result<i64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let match_ids = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM matches
+        WHERE status = 'pending' AND confirm_deadline <= NOW()
+        ORDER BY confirm_deadline
+        FOR UPDATE SKIP LOCKED
+        "#
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for match_id in &match_ids {
+        finalize_match(&mut tx, *match_id).await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(match_ids.len() as i64)
+}
+
+/// Settle a disputed match as `Finalized` or `Voided`, recording the admin's note
+pub async fn resolve_disputed_match(
+    pool: &DatabasePool,
+    match_id: Uuid,
+    outcome: MatchStatus,
+    resolved_by: Uuid,
+    note: Option<&str>,
+) -> Result<Option<DbMatch>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let resolved = sqlx::query!(
+        r#"
+        UPDATE matches SET resolved_by = $2, resolution_note = $3, updated_at = NOW()
+        WHERE id = $1 AND status = 'disputed'
+        "#,
+        match_id,
+        resolved_by,
+        note
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if resolved.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    let db_match = if outcome == MatchStatus::Finalized {
+        finalize_match(&mut tx, match_id).await?
+    } else {
+        sqlx::query_as!(
+            DbMatch,
+            r#"
+            UPDATE matches SET status = 'voided'
+            WHERE id = $1
+            RETURNING id, discord_org_id, game_type, reported_by, started_at, ended_at, status as "status: String", confirm_deadline, finalized_at, resolved_by, resolution_note, created_at, updated_at
+            "#,
+            match_id
+        )
+        .fetch_one(&mut *tx)
+        .await?
+    };
+
+    tx.commit().await?;
+
+    Ok(Some(db_match))
+}
+
+/// Count a user's finalized matches and average their placement, optionally within one
+/// org and game type and matches that ended in `[from, until)`; matches in deleted orgs
+/// don't count. With a `viewer_id`, only matches from orgs the viewer owns or is an
+/// unbanned member of are counted.
+pub async fn get_user_match_stats(
+    pool: &DatabasePool,
+    user_id: Uuid,
+    org_id: Option<Uuid>,
+    game_type: Option<&str>,
+    period: Option<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)>,
+    viewer_id: Option<Uuid>,
+) -> Result<(i64, Option<f64>), sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "total_games!", AVG(mp.placement)::float8 as average_placement
+        FROM match_participants mp
+        JOIN matches m ON m.id = mp.match_id
+        JOIN discord_orgs o ON o.id = m.discord_org_id
+        WHERE mp.user_id = $1
+            AND m.status = 'finalized'
+            AND o.deleted_at IS NULL
+            AND ($2::uuid IS NULL OR m.discord_org_id = $2)
+            AND ($3::text IS NULL OR m.game_type = $3)
+            AND ($4::timestamptz IS NULL OR m.ended_at >= $4)
+            AND ($5::timestamptz IS NULL OR m.ended_at < $5)
+            AND (
+                $6::uuid IS NULL
+                OR o.owner_id = $6
+                OR EXISTS (
+                    SELECT 1 FROM members v
+                    WHERE v.discord_org_id = m.discord_org_id AND v.user_id = $6 AND v.status <> 'banned'
+                )
+            )
+        "#,
+        user_id,
+        org_id,
+        game_type,
+        period.map(|(from, _)| from),
+        period.map(|(_, until)| until),
+        viewer_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok((row.total_games, row.average_placement))
+}
+
+// Rating queries
+
+/// Serialize rating updates within one org and game type until the transaction ends
+async fn lock_ratings(
+    conn: &mut sqlx::PgConnection,
+    org_id: Uuid,
+    game_type: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "SELECT pg_advisory_xact_lock(hashtext($1::text || ':' || $2))",
+        org_id.to_string(),
+        game_type
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Rate a finalized match, updating each participant's rating and recording the change
+async fn apply_match_ratings(
+    conn: &mut sqlx::PgConnection,
+    db_match: &DbMatch,
+) -> Result<(), sqlx::Error> {
+    lock_ratings(conn, db_match.discord_org_id, &db_match.game_type).await?;
+
+    let participants = sqlx::query!(
+        "SELECT user_id, placement FROM match_participants WHERE match_id = $1 ORDER BY user_id",
+        db_match.id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let user_ids: Vec<Uuid> = participants.iter().map(|p| p.user_id).collect();
+
+    // New players start from the default rating
+    sqlx::query!(
+        r#"
+        INSERT INTO ratings (discord_org_id, game_type, user_id)
+        SELECT $1, $2, user_id FROM UNNEST($3::uuid[]) AS user_id
+        ON CONFLICT (discord_org_id, game_type, user_id) DO NOTHING
+        "#,
+        db_match.discord_org_id,
+        db_match.game_type,
+        &user_ids
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let ratings = sqlx::query_as!(
+        DbRating,
+        r#"
+        SELECT id, discord_org_id, user_id, game_type, rating, deviation, volatility, games_played, created_at, updated_at
+        FROM ratings
+        WHERE discord_org_id = $1 AND game_type = $2 AND user_id = ANY($3)
+        ORDER BY user_id
+        "#,
+        db_match.discord_org_id,
+        db_match.game_type,
+        &user_ids
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    // Both lists are ordered by user_id
+    let players: Vec<(Rating, i32)> = ratings
+        .iter()
+        .zip(&participants)
+        .map(|(db_rating, participant)| (Rating::from(db_rating), participant.placement))
+        .collect();
+
+    let Some(rated) = rating::rate_match(&Glicko2::default(), &players) else {
+        return Ok(()); // Not a match the rating system handles
+    };
+
+    for (db_rating, new_rating) in ratings.iter().zip(rated) {
+        sqlx::query!(
+            r#"
+            UPDATE ratings
+            SET rating = $2, deviation = $3, volatility = $4, games_played = games_played + 1
+            WHERE id = $1
+            "#,
+            db_rating.id,
+            new_rating.rating,
+            new_rating.deviation,
+            new_rating.volatility
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO rating_history (
+                match_id, discord_org_id, user_id, game_type,
+                rating_before, deviation_before, volatility_before,
+                rating_after, deviation_after, volatility_after
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+            db_match.id,
+            db_match.discord_org_id,
+            db_rating.user_id,
+            db_match.game_type,
+            db_rating.rating,
+            db_rating.deviation,
+            db_rating.volatility,
+            new_rating.rating,
+            new_rating.deviation,
+            new_rating.volatility
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Get an org's ratings for one game type, highest first; deleted users are left out
+pub async fn get_leaderboard(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    game_type: &str,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<DbLeaderboardEntry>, sqlx::Error> {
+    let entries = sqlx::query_as!(
+        DbLeaderboardEntry,
+        r#"
+        SELECT
+            RANK() OVER (ORDER BY r.rating DESC) as "rank!",
+            r.discord_org_id,
+            r.game_type,
+            r.rating,
+            r.deviation,
+            r.volatility,
+            r.games_played,
+            r.updated_at,
+
+            u.id as user_id,
+            u.discord_id as user_discord_id,
+            u.display_name as user_display_name,
+            u.avatar_url as user_avatar_url,
+            u.bio as user_bio,
+            u.created_at as user_created_at,
+            u.updated_at as user_updated_at
+        FROM ratings r
+        JOIN users u ON u.id = r.user_id
+        WHERE r.discord_org_id = $1 AND r.game_type = $2 AND u.deleted_at IS NULL
+        ORDER BY r.rating DESC, u.display_name
+        LIMIT $3 OFFSET $4
+        "#,
+        org_id,
+        game_type,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(entries)
+}
+
+pub async fn get_rating(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    game_type: &str,
+    user_id: Uuid,
+) -> Result<Option<DbRating>, sqlx::Error> {
+    let rating = sqlx::query_as!(
+        DbRating,
+        r#"
+        SELECT id, discord_org_id, user_id, game_type, rating, deviation, volatility, games_played, created_at, updated_at
+        FROM ratings
+        WHERE discord_org_id = $1 AND game_type = $2 AND user_id = $3
+        "#,
+        org_id,
+        game_type,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(rating)
+}
+
+/// Get every rating change of a player in one org and game type, in the order their
+/// matches are rated (by when they ended)
+pub async fn get_rating_history(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    game_type: &str,
+    user_id: Uuid,
+) -> Result<Vec<DbRatingHistory>, sqlx::Error> {
+    let history = sqlx::query_as!(
+        DbRatingHistory,
+        r#"
+        SELECT rh.id, rh.match_id, rh.discord_org_id, rh.user_id, rh.game_type, rh.rating_before, rh.deviation_before, rh.volatility_before, rh.rating_after, rh.deviation_after, rh.volatility_after, rh.created_at
+        FROM rating_history rh
+        JOIN matches m ON m.id = rh.match_id
+        WHERE rh.discord_org_id = $1 AND rh.game_type = $2 AND rh.user_id = $3
+        ORDER BY m.ended_at, m.finalized_at, m.id
+        "#,
+        org_id,
+        game_type,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(history)
+}
+
+/// Rebuild an org's ratings for one game type by replaying its finalized matches in
+/// the order they ended, soft resetting them wherever an archived season ended;
+/// returns how many matches were replayed
+pub async fn recompute_ratings(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    game_type: &str,
+) -> Result<i64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let replayed = replay_ratings(&mut tx, org_id, game_type).await?;
+
+    tx.commit().await?;
+
+    Ok(replayed)
+}
+
+async fn replay_ratings(
+    conn: &mut sqlx::PgConnection,
+    org_id: Uuid,
+    game_type: &str,
+) -> Result<i64, sqlx::Error> {
+    lock_ratings(conn, org_id, game_type).await?;
+
+    sqlx::query!(
+        "DELETE FROM rating_history WHERE discord_org_id = $1 AND game_type = $2",
+        org_id,
+        game_type
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM ratings WHERE discord_org_id = $1 AND game_type = $2",
+        org_id,
+        game_type
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    let matches = sqlx::query_as!(
+        DbMatch,
+        r#"
+        SELECT id, discord_org_id, game_type, reported_by, started_at, ended_at, status as "status: String", confirm_deadline, finalized_at, resolved_by, resolution_note, created_at, updated_at
+        FROM matches
+        WHERE discord_org_id = $1 AND game_type = $2 AND status = 'finalized'
+        ORDER BY ended_at, finalized_at, id
+        "#,
+        org_id,
+        game_type
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let season_ends = sqlx::query!(
+        r#"
+        SELECT ends_at, soft_reset FROM seasons
+        WHERE discord_org_id = $1 AND archived_at IS NOT NULL
+        ORDER BY ends_at
+        "#,
+        org_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let mut season_ends = season_ends.iter().peekable();
+
+    for db_match in &matches {
+        while let Some(season) = season_ends.next_if(|season| season.ends_at <= db_match.ended_at) {
+            soft_reset_ratings(conn, org_id, game_type, season.soft_reset).await?;
+        }
+        apply_match_ratings(conn, db_match).await?;
+    }
+    for season in season_ends {
+        soft_reset_ratings(conn, org_id, game_type, season.soft_reset).await?;
+    }
+
+    Ok(matches.len() as i64)
+}
+
+/// Pull every rating of one org and game type back towards the default by `strength`
+async fn soft_reset_ratings(
+    conn: &mut sqlx::PgConnection,
+    org_id: Uuid,
+    game_type: &str,
+    strength: f64,
+) -> Result<(), sqlx::Error> {
+    let ratings = sqlx::query_as!(
+        DbRating,
+        r#"
+        SELECT id, discord_org_id, user_id, game_type, rating, deviation, volatility, games_played, created_at, updated_at
+        FROM ratings
+        WHERE discord_org_id = $1 AND game_type = $2
+        "#,
+        org_id,
+        game_type
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for db_rating in &ratings {
+        let reset = Rating::from(db_rating).soft_reset(strength);
+
+        sqlx::query!(
+            "UPDATE ratings SET rating = $2, deviation = $3 WHERE id = $1",
+            db_rating.id,
+            reset.rating,
+            reset.deviation
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+// Season queries
+
+/// Create a season unless it overlaps one of the org's other seasons, in which case
+/// `None` is returned
+pub async fn create_season(
+    pool: &DatabasePool,
+    season: CreateSeason,
+) -> Result<Option<DbSeason>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    // Serialize season creation per org so two overlapping seasons can't both pass
+    // the check
+    sqlx::query!(
+        "SELECT pg_advisory_xact_lock(hashtext('seasons:' || $1::text))",
+        season.discord_org_id.to_string()
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let db_season = sqlx::query_as!(
+        DbSeason,
+        r#"
+        INSERT INTO seasons (discord_org_id, name, starts_at, ends_at, soft_reset, created_by)
+        SELECT $1::uuid, $2::varchar, $3::timestamptz, $4::timestamptz, $5::float8, $6::uuid
+        WHERE NOT EXISTS (
+            SELECT 1 FROM seasons
+            WHERE discord_org_id = $1 AND starts_at < $4 AND ends_at > $3
+        )
+        RETURNING id, discord_org_id, name, starts_at, ends_at, soft_reset, created_by, archived_at, created_at, updated_at
+        "#,
+        season.discord_org_id,
+        season.name,
+        season.starts_at,
+        season.ends_at,
+        season.soft_reset,
+        season.created_by
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(db_season)
+}
+
+pub async fn get_season_by_id(
+    pool: &DatabasePool,
+    season_id: Uuid,
+) -> Result<Option<DbSeason>, sqlx::Error> {
+    let season = sqlx::query_as!(
+        DbSeason,
+        r#"
+        SELECT id, discord_org_id, name, starts_at, ends_at, soft_reset, created_by, archived_at, created_at, updated_at
+        FROM seasons
+        WHERE id = $1
+        "#,
+        season_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(season)
+}
+
+/// Get an org's seasons, latest first
+pub async fn get_seasons_by_org(
+    pool: &DatabasePool,
+    org_id: Uuid,
+) -> Result<Vec<DbSeason>, sqlx::Error> {
+    let seasons = sqlx::query_as!(
+        DbSeason,
+        r#"
+        SELECT id, discord_org_id, name, starts_at, ends_at, soft_reset, created_by, archived_at, created_at, updated_at
+        FROM seasons
+        WHERE discord_org_id = $1
+        ORDER BY starts_at DESC
+        "#,
+        org_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(seasons)
+}
+
+/// Delete a season that hasn't rolled over yet; archived seasons are kept since
+/// ratings were already reset at their end
+pub async fn delete_season(pool: &DatabasePool, season_id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM seasons WHERE id = $1 AND archived_at IS NULL",
+        season_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Roll over every season that has ended once no match from its period is still
+/// pending or disputed; returns how many seasons rolled over
+pub async fn roll_over_ended_seasons(pool: &DatabasePool) -> Result<i64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let seasons = sqlx::query_as!(
+        DbSeason,
+        r#"
+        SELECT id, discord_org_id, name, starts_at, ends_at, soft_reset, created_by, archived_at, created_at, updated_at
+        FROM seasons s
+        WHERE archived_at IS NULL
+            AND ends_at <= NOW()
+            AND NOT EXISTS (
+                SELECT 1 FROM matches m
+                WHERE m.discord_org_id = s.discord_org_id
+                    AND m.status IN ('pending', 'disputed')
+                    AND m.ended_at < s.ends_at
+            )
+        ORDER BY ends_at
+        FOR UPDATE SKIP LOCKED
+        "#
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for season in &seasons {
+        archive_season(&mut tx, season).await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(seasons.len() as i64)
+}
+
+/// Freeze a season's final leaderboards for every game type, then soft reset the
+/// org's ratings for the next season at the moment the season ended, as a recompute would
+async fn archive_season(
+    conn: &mut sqlx::PgConnection,
+    season: &DbSeason,
+) -> Result<(), sqlx::Error> {
+    let game_types = sqlx::query_scalar!(
+        "SELECT DISTINCT game_type FROM ratings WHERE discord_org_id = $1 ORDER BY game_type",
+        season.discord_org_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for game_type in &game_types {
+        lock_ratings(conn, season.discord_org_id, game_type).await?;
+    }
+
+    // Only players who played during the season make its standings, with their rating
+    // after the last match that ended before the season did (matches that ended later may
+    // already have been rated)
+    sqlx::query!(
+        r#"
+        WITH season_games AS (
+            SELECT mp.user_id, m.game_type, COUNT(*)::int as games_played
+            FROM match_participants mp
+            JOIN matches m ON m.id = mp.match_id
+            WHERE m.discord_org_id = $2
+                AND m.status = 'finalized'
+                AND m.ended_at >= $3
+                AND m.ended_at < $4
+            GROUP BY mp.user_id, m.game_type
+        ),
+        final_ratings AS (
+            SELECT DISTINCT ON (rh.user_id, rh.game_type)
+                rh.user_id, rh.game_type, rh.rating_after, rh.deviation_after, rh.volatility_after
+            FROM rating_history rh
+            JOIN matches m ON m.id = rh.match_id
+            WHERE rh.discord_org_id = $2 AND m.ended_at < $4
+            ORDER BY rh.user_id, rh.game_type, m.ended_at DESC, m.finalized_at DESC, m.id DESC
+        )
+        INSERT INTO season_standings (
+            season_id, game_type, user_id, rank, rating, deviation, volatility, games_played
+        )
+        SELECT
+            $1,
+            fr.game_type,
+            fr.user_id,
+            RANK() OVER (PARTITION BY fr.game_type ORDER BY fr.rating_after DESC)::int,
+            fr.rating_after,
+            fr.deviation_after,
+            fr.volatility_after,
+            sg.games_played
+        FROM final_ratings fr
+        JOIN season_games sg ON sg.user_id = fr.user_id AND sg.game_type = fr.game_type
+        "#,
+        season.id,
+        season.discord_org_id,
+        season.starts_at,
+        season.ends_at
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE seasons SET archived_at = NOW() WHERE id = $1",
+        season.id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    // Game types with matches rated past the season's end are replayed, which applies
+    // the reset where the season ended; the rest can be reset in place
+    for game_type in &game_types {
+        let rated_after_end = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM matches
+                WHERE discord_org_id = $1 AND game_type = $2 AND status = 'finalized' AND ended_at >= $3
+            ) as "exists!"
+            "#,
+            season.discord_org_id,
+            game_type,
+            season.ends_at
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        if rated_after_end {
+            replay_ratings(conn, season.discord_org_id, game_type).await?;
+        } else {
+            soft_reset_ratings(conn, season.discord_org_id, game_type, season.soft_reset).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Get an archived season's final standings for one game type, best first; deleted
+/// users are left out
+pub async fn get_season_standings(
+    pool: &DatabasePool,
+    season_id: Uuid,
+    game_type: &str,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<DbLeaderboardEntry>, sqlx::Error> {
+    let entries = sqlx::query_as!(
+        DbLeaderboardEntry,
+        r#"
+        SELECT
+            ss.rank::bigint as "rank!",
+            s.discord_org_id,
+            ss.game_type,
+            ss.rating,
+            ss.deviation,
+            ss.volatility,
+            ss.games_played,
+            ss.created_at as updated_at,
+
+            u.id as user_id,
+            u.discord_id as user_discord_id,
+            u.display_name as user_display_name,
+            u.avatar_url as user_avatar_url,
+            u.bio as user_bio,
+            u.created_at as user_created_at,
+            u.updated_at as user_updated_at
+        FROM season_standings ss
+        JOIN seasons s ON s.id = ss.season_id
+        JOIN users u ON u.id = ss.user_id
+        WHERE ss.season_id = $1 AND ss.game_type = $2 AND u.deleted_at IS NULL
+        ORDER BY ss.rank, u.display_name
+        LIMIT $3 OFFSET $4
+        "#,
+        season_id,
+        game_type,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(entries)
 }
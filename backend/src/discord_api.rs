@@ -0,0 +1,182 @@
+//! Calls to Discord's OAuth2 endpoints and user API made outside the login flow
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::config::Config;
+
+/// Token grant returned by Discord's token endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscordTokenGrant {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub token_type: Option<String>,
+    pub scope: Option<String>,
+    pub expires_in: Option<i64>,
+}
+
+/// Discord's `MANAGE_GUILD` ("Manage Server") permission bit
+const MANAGE_GUILD: u64 = 1 << 5;
+/// Discord's `ADMINISTRATOR` permission bit, which implies every other permission
+const ADMINISTRATOR: u64 = 1 << 3;
+
+/// Partial guild returned by `GET /users/@me/guilds`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PartialGuild {
+    pub id: String,
+    pub name: String,
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub owner: bool,
+    #[serde(default)]
+    pub permissions: String, // Permission bitset serialized as a decimal string
+}
+
+impl PartialGuild {
+    /// Whether the user owns the guild or holds Manage Server in it
+    pub fn can_manage(&self) -> bool {
+        let permissions = self.permissions.parse::<u64>().unwrap_or(0);
+        self.owner || permissions & (MANAGE_GUILD | ADMINISTRATOR) != 0
+    }
+
+    /// CDN URL of the guild icon, if it has one
+    pub fn icon_url(&self) -> Option<String> {
+        self.icon
+            .as_ref()
+            .map(|icon| format!("https://cdn.discordapp.com/icons/{}/{}.png", self.id, icon))
+    }
+}
+
+/// Why a call to Discord failed
+#[derive(Debug)]
+pub enum DiscordApiError {
+    /// Discord answered with a 4xx: the credential is no longer usable
+    Rejected(String),
+    /// Network error or 5xx: worth retrying later
+    Transient(String),
+}
+
+impl std::fmt::Display for DiscordApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiscordApiError::Rejected(e) => write!(f, "rejected by Discord: {}", e),
+            DiscordApiError::Transient(e) => write!(f, "temporary failure: {}", e),
+        }
+    }
+}
+
+/// Run the `refresh_token` grant against `Config::discord_token_url()`
+pub async fn refresh_access_token(refresh_token: &str) -> Result<DiscordTokenGrant, DiscordApiError> {
+    let mut params = HashMap::new();
+    params.insert("client_id", Config::discord_client_id());
+    params.insert("client_secret", Config::discord_client_secret());
+    params.insert("grant_type", "refresh_token".to_string());
+    params.insert("refresh_token", refresh_token.to_string());
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(Config::discord_token_url())
+        .form(&params)
+        .send()
+        .await
+        .map_err(|e| DiscordApiError::Transient(format!("Request failed: {}", e)))?;
+
+    let status = response.status();
+    if status.is_success() {
+        response
+            .json()
+            .await
+            .map_err(|e| DiscordApiError::Transient(format!("JSON parsing failed: {}", e)))
+    } else {
+        let error_text = response.text().await.unwrap_or_default();
+        let message = format!("Token refresh failed: {} - {}", status, error_text);
+        if status.is_client_error() {
+            Err(DiscordApiError::Rejected(message))
+        } else {
+            Err(DiscordApiError::Transient(message))
+        }
+    }
+}
+
+/// Revoke an access or refresh token via `Config::discord_revoke_url()`
+///
+/// Revoking the refresh token also invalidates every access token issued from it.
+pub async fn revoke_token(token: &str, token_type_hint: &str) -> Result<(), DiscordApiError> {
+    let mut params = HashMap::new();
+    params.insert("client_id", Config::discord_client_id());
+    params.insert("client_secret", Config::discord_client_secret());
+    params.insert("token", token.to_string());
+    params.insert("token_type_hint", token_type_hint.to_string());
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(Config::discord_revoke_url())
+        .form(&params)
+        .send()
+        .await
+        .map_err(|e| DiscordApiError::Transient(format!("Request failed: {}", e)))?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        let error_text = response.text().await.unwrap_or_default();
+        let message = format!("Token revocation failed: {} - {}", status, error_text);
+        if status.is_client_error() {
+            Err(DiscordApiError::Rejected(message))
+        } else {
+            Err(DiscordApiError::Transient(message))
+        }
+    }
+}
+
+/// List the guilds the token's user belongs to (needs the `guilds` scope)
+pub async fn list_user_guilds(access_token: &str) -> Result<Vec<PartialGuild>, DiscordApiError> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(Config::discord_api_endpoint("users/@me/guilds"))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .map_err(|e| DiscordApiError::Transient(format!("Request failed: {}", e)))?;
+
+    let status = response.status();
+    if status.is_success() {
+        response
+            .json()
+            .await
+            .map_err(|e| DiscordApiError::Transient(format!("JSON parsing failed: {}", e)))
+    } else {
+        let error_text = response.text().await.unwrap_or_default();
+        let message = format!("Guild listing failed: {} - {}", status, error_text);
+        if status.is_client_error() {
+            Err(DiscordApiError::Rejected(message))
+        } else {
+            Err(DiscordApiError::Transient(message))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guild(owner: bool, permissions: &str) -> PartialGuild {
+        PartialGuild {
+            id: "1".to_string(),
+            name: "Guild".to_string(),
+            icon: None,
+            owner,
+            permissions: permissions.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_can_manage() {
+        assert!(guild(true, "0").can_manage());
+        assert!(guild(false, "32").can_manage()); // MANAGE_GUILD
+        assert!(guild(false, "8").can_manage()); // ADMINISTRATOR
+        assert!(!guild(false, "2147483648").can_manage());
+        assert!(!guild(false, "").can_manage());
+    }
+}
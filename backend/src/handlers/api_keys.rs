@@ -0,0 +1,210 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::expires_at_from_now;
+use super::users::ApiResponse;
+use crate::auth::AuthUser;
+use crate::auth::api_key::generate_api_key;
+use crate::auth::permissions;
+use crate::database::queries;
+use shared::database::{CreateApiKey, DbApiKey};
+use shared::models::{ApiKeyScope, Permissions};
+
+const MAX_API_KEY_TTL_SECONDS: i64 = 365 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyResponse {
+    pub id: Uuid,
+    pub discord_org_id: Uuid,
+    pub created_by: Option<Uuid>,
+    pub name: String,
+    pub key_prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    // Note: The key itself is only returned once, by CreatedApiKeyResponse
+}
+
+impl From<DbApiKey> for ApiKeyResponse {
+    fn from(key: DbApiKey) -> Self {
+        Self {
+            id: key.id,
+            discord_org_id: key.discord_org_id,
+            created_by: key.created_by,
+            name: key.name,
+            key_prefix: key.key_prefix,
+            scopes: key.scopes,
+            expires_at: key.expires_at,
+            last_used_at: key.last_used_at,
+            revoked_at: key.revoked_at,
+            created_at: key.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatedApiKeyResponse {
+    pub key: String,
+    #[serde(flatten)]
+    pub api_key: ApiKeyResponse,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub expires_in: Option<i64>, // seconds from now
+}
+
+/// Create an API key for an org; the plaintext key is only returned in this response
+pub async fn create_api_key(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_API_KEYS,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    if request.name.trim().is_empty() || request.scopes.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<CreatedApiKeyResponse>::error(
+                "An API key needs a name and at least one scope".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    let expires_at = match expires_at_from_now(request.expires_in, MAX_API_KEY_TTL_SECONDS) {
+        Ok(expires_at) => expires_at,
+        Err(error) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::<CreatedApiKeyResponse>::error(error)),
+            )
+                .into_response();
+        }
+    };
+
+    let mut scopes: Vec<String> = request
+        .scopes
+        .iter()
+        .map(|scope| scope.as_str().to_string())
+        .collect();
+    scopes.sort();
+    scopes.dedup();
+
+    let generated = generate_api_key();
+    let create_key = CreateApiKey {
+        discord_org_id: org_id,
+        created_by: Some(auth.user_id),
+        name: request.name.trim().to_string(),
+        key_prefix: generated.key_prefix,
+        key_hash: generated.key_hash,
+        scopes,
+        expires_at,
+    };
+
+    match queries::create_api_key(&state.db_pool, create_key).await {
+        Ok(db_key) => {
+            let response = CreatedApiKeyResponse {
+                key: generated.key,
+                api_key: db_key.into(),
+            };
+            (StatusCode::CREATED, Json(ApiResponse::success(response))).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<CreatedApiKeyResponse>::error(format!(
+                "Failed to create API key: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// List an org's API keys, including revoked and expired ones
+pub async fn list_api_keys(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_API_KEYS,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    match queries::get_api_keys_by_org(&state.db_pool, org_id).await {
+        Ok(db_keys) => {
+            let keys: Vec<ApiKeyResponse> = db_keys.into_iter().map(Into::into).collect();
+            (StatusCode::OK, Json(ApiResponse::success(keys))).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<ApiKeyResponse>>::error(format!(
+                "Database error: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Revoke an org API key
+pub async fn revoke_api_key(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, key_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_API_KEYS,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    match queries::revoke_api_key(&state.db_pool, org_id, key_id).await {
+        Ok(true) => (StatusCode::OK, Json(ApiResponse::success(()))).into_response(),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::error("API key not found".to_string())),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to revoke API key: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
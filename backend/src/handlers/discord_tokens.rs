@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::auth::AuthUser;
 use crate::database::{DatabasePool, queries};
 use shared::database::{CreateDiscordToken, UpdateDiscordToken};
 
@@ -43,6 +44,7 @@ pub struct DiscordTokenResponse {
     pub token_type: String,
     pub scope: Option<String>,
     pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub invalidated_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     // Note: We don't include access_token and refresh_token in responses for security
@@ -56,6 +58,7 @@ impl From<shared::database::DbDiscordToken> for DiscordTokenResponse {
             token_type: token.token_type,
             scope: token.scope,
             expires_at: token.expires_at,
+            invalidated_at: token.invalidated_at,
             created_at: token.created_at,
             updated_at: token.updated_at,
         }
@@ -75,8 +78,18 @@ pub struct CreateTokenRequest {
 /// Create or update a Discord token for a user (upsert)
 pub async fn upsert_discord_token(
     State(state): State<crate::AppState>,
+    auth: AuthUser,
     Json(request): Json<CreateTokenRequest>,
 ) -> impl IntoResponse {
+    if !auth.can_act_for(request.user_id) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<DiscordTokenResponse>::error(
+                "You can only access your own Discord token".to_string(),
+            )),
+        );
+    }
+
     let expires_at = request
         .expires_in
         .map(|seconds| chrono::Utc::now() + chrono::Duration::seconds(seconds));
@@ -112,8 +125,18 @@ pub async fn upsert_discord_token(
 /// Get Discord token info for a user (without sensitive data)
 pub async fn get_discord_token_by_user(
     State(state): State<crate::AppState>,
+    auth: AuthUser,
     Path(user_id): Path<Uuid>,
 ) -> impl IntoResponse {
+    if !auth.can_act_for(user_id) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<DiscordTokenResponse>::error(
+                "You can only access your own Discord token".to_string(),
+            )),
+        );
+    }
+
     match queries::get_discord_token_by_user_id(&state.db_pool, user_id).await {
         Ok(Some(db_token)) => {
             let response_token: DiscordTokenResponse = db_token.into();
@@ -138,9 +161,19 @@ pub async fn get_discord_token_by_user(
 /// Update Discord token for a user
 pub async fn update_discord_token(
     State(state): State<crate::AppState>,
+    auth: AuthUser,
     Path(user_id): Path<Uuid>,
     Json(update_token): Json<UpdateDiscordToken>,
 ) -> impl IntoResponse {
+    if !auth.can_act_for(user_id) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<DiscordTokenResponse>::error(
+                "You can only access your own Discord token".to_string(),
+            )),
+        );
+    }
+
     match queries::update_discord_token(&state.db_pool, user_id, update_token).await {
         Ok(Some(db_token)) => {
             let response_token: DiscordTokenResponse = db_token.into();
@@ -165,8 +198,18 @@ pub async fn update_discord_token(
 /// Delete Discord token for a user
 pub async fn delete_discord_token_by_user(
     State(state): State<crate::AppState>,
+    auth: AuthUser,
     Path(user_id): Path<Uuid>,
 ) -> impl IntoResponse {
+    if !auth.can_act_for(user_id) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<()>::error(
+                "You can only access your own Discord token".to_string(),
+            )),
+        );
+    }
+
     match queries::delete_discord_token_by_user_id(&state.db_pool, user_id).await {
         Ok(true) => (StatusCode::NO_CONTENT, Json(ApiResponse::<()>::success(()))),
         Ok(false) => (
@@ -188,8 +231,18 @@ pub async fn delete_discord_token_by_user(
 /// Verify if a Discord token exists and is valid
 pub async fn verify_discord_token(
     State(state): State<crate::AppState>,
+    auth: AuthUser,
     Path(user_id): Path<Uuid>,
 ) -> impl IntoResponse {
+    if !auth.can_act_for(user_id) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<serde_json::Value>::error(
+                "You can only access your own Discord token".to_string(),
+            )),
+        );
+    }
+
     match queries::get_discord_token_by_user_id(&state.db_pool, user_id).await {
         Ok(Some(token)) => {
             // Check if token is expired
@@ -201,6 +254,7 @@ pub async fn verify_discord_token(
                 "user_id": user_id,
                 "has_token": true,
                 "is_expired": !is_valid,
+                "is_invalidated": token.invalidated_at.is_some(),
                 "expires_at": token.expires_at
             });
 
@@ -214,6 +268,7 @@ pub async fn verify_discord_token(
                 "user_id": user_id,
                 "has_token": false,
                 "is_expired": null,
+                "is_invalidated": null,
                 "expires_at": null
             });
 
@@ -233,7 +288,19 @@ pub async fn verify_discord_token(
 }
 
 /// Cleanup expired tokens (admin endpoint)
-pub async fn cleanup_expired_tokens(State(state): State<crate::AppState>) -> impl IntoResponse {
+pub async fn cleanup_expired_tokens(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+) -> impl IntoResponse {
+    if !auth.is_admin {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<serde_json::Value>::error(
+                "Only admins can clean up tokens".to_string(),
+            )),
+        );
+    }
+
     match queries::cleanup_expired_tokens(&state.db_pool).await {
         Ok(deleted_count) => {
             let result = serde_json::json!({
@@ -270,6 +337,11 @@ pub async fn validate_access_token(
     let token = queries::get_discord_token_by_access_token(pool, access_token).await?;
 
     if let Some(ref token) = token {
+        // Check if Discord rejected the token's refresh
+        if token.invalidated_at.is_some() {
+            return Ok(None);
+        }
+
         // Check if token is expired
         if let Some(expires_at) = token.expires_at {
             if expires_at <= chrono::Utc::now() {
@@ -0,0 +1,173 @@
+//! Importing Discord guilds as orgs
+//!
+//! Guilds are listed with the user's stored Discord token (which needs the
+//! `guilds` scope); only guilds they own or hold Manage Server in can be imported.
+
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::orgs::org_response;
+use super::users::ApiResponse;
+use crate::auth::AuthUser;
+use crate::database::{DatabasePool, queries};
+use crate::discord_api::{self, DiscordApiError, PartialGuild};
+use shared::database::CreateDiscordOrg;
+use shared::models::{DiscordGuild, DiscordOrg};
+
+#[derive(Debug, Deserialize)]
+pub struct ImportGuildRequest {
+    pub guild_id: String,
+    pub description: Option<String>,
+    pub requires_approval: Option<bool>, // Defaults to false
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(ApiResponse::<()>::error(message.to_string()))).into_response()
+}
+
+/// Fetch the guilds the user can manage, using their stored Discord token
+async fn manageable_guilds(
+    pool: &DatabasePool,
+    user_id: Uuid,
+) -> Result<Vec<PartialGuild>, Response> {
+    let token = match queries::get_discord_token_by_user_id(pool, user_id).await {
+        Ok(Some(token)) if token.invalidated_at.is_none() => token,
+        Ok(_) => {
+            return Err(error_response(
+                StatusCode::UNAUTHORIZED,
+                "No usable Discord token; log in with Discord again",
+            ));
+        }
+        Err(e) => {
+            return Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                &format!("Database error: {}", e),
+            ));
+        }
+    };
+
+    let has_guilds_scope = token
+        .scope
+        .as_deref()
+        .is_some_and(|scope| scope.split_whitespace().any(|s| s == "guilds"));
+    if !has_guilds_scope {
+        return Err(error_response(
+            StatusCode::FORBIDDEN,
+            "Your Discord login doesn't include the `guilds` scope; log in with Discord again",
+        ));
+    }
+
+    match discord_api::list_user_guilds(&token.access_token).await {
+        Ok(guilds) => Ok(guilds
+            .into_iter()
+            .filter(PartialGuild::can_manage)
+            .collect()),
+        Err(DiscordApiError::Rejected(e)) => {
+            println!(
+                "⚠️  Discord rejected guild listing for user {}: {}",
+                user_id, e
+            );
+            Err(error_response(
+                StatusCode::UNAUTHORIZED,
+                "Discord rejected your token; log in with Discord again",
+            ))
+        }
+        Err(DiscordApiError::Transient(e)) => Err(error_response(
+            StatusCode::BAD_GATEWAY,
+            &format!("Failed to reach Discord: {}", e),
+        )),
+    }
+}
+
+/// List the guilds the caller can import, with the org each is already bound to
+pub async fn list_guilds(State(state): State<crate::AppState>, auth: AuthUser) -> Response {
+    let guilds = match manageable_guilds(&state.db_pool, auth.user_id).await {
+        Ok(guilds) => guilds,
+        Err(response) => return response,
+    };
+
+    let guild_ids: Vec<String> = guilds.iter().map(|guild| guild.id.clone()).collect();
+    let imported: HashMap<String, Uuid> =
+        match queries::get_discord_orgs_by_guild_ids(&state.db_pool, &guild_ids).await {
+            Ok(orgs) => orgs
+                .into_iter()
+                .filter_map(|org| org.guild_id.map(|guild_id| (guild_id, org.id)))
+                .collect(),
+            Err(e) => {
+                return error_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    &format!("Database error: {}", e),
+                );
+            }
+        };
+
+    let guilds: Vec<DiscordGuild> = guilds
+        .into_iter()
+        .map(|guild| DiscordGuild {
+            icon_url: guild.icon_url(),
+            org_id: imported.get(&guild.id).copied(),
+            id: guild.id,
+            name: guild.name,
+        })
+        .collect();
+
+    (StatusCode::OK, Json(ApiResponse::success(guilds))).into_response()
+}
+
+/// Create an org, owned by the caller, from one of their manageable guilds
+pub async fn import_guild(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Json(request): Json<ImportGuildRequest>,
+) -> Response {
+    let guilds = match manageable_guilds(&state.db_pool, auth.user_id).await {
+        Ok(guilds) => guilds,
+        Err(response) => return response,
+    };
+
+    let Some(guild) = guilds
+        .into_iter()
+        .find(|guild| guild.id == request.guild_id)
+    else {
+        return error_response(
+            StatusCode::FORBIDDEN,
+            "You need the Manage Server permission in that guild to import it",
+        );
+    };
+
+    let create_org = CreateDiscordOrg {
+        owner_id: auth.user_id,
+        avatar_url: guild.icon_url(),
+        name: guild.name,
+        description: request.description,
+        guild_id: Some(guild.id),
+        requires_approval: request.requires_approval.unwrap_or(false),
+        listed: false,
+        tags: Vec::new(),
+    };
+
+    match queries::create_discord_org(&state.db_pool, create_org).await {
+        Ok(db_org) => org_response(&state.db_pool, db_org, StatusCode::CREATED).await,
+        Err(e) => {
+            let (status, error_msg) = if e.to_string().contains("duplicate key") {
+                (
+                    StatusCode::CONFLICT,
+                    "This guild has already been imported".to_string(),
+                )
+            } else {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Failed to import guild: {}", e),
+                )
+            };
+            (status, Json(ApiResponse::<DiscordOrg>::error(error_msg))).into_response()
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use rand::{Rng, distributions::Alphanumeric};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::expires_at_from_now;
+use super::members::load_member;
+use super::users::ApiResponse;
+use crate::auth::AuthUser;
+use crate::auth::permissions;
+use crate::database::queries::{self, InviteRedemption};
+use shared::database::{CreateOrgInvite, DbInviteRedemptionWithUser, DbOrgInvite};
+use shared::models::{Member, MemberStatus, Permissions, User};
+
+const INVITE_CODE_LENGTH: usize = 10;
+const MAX_INVITE_TTL_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteResponse {
+    pub id: Uuid,
+    pub discord_org_id: Uuid,
+    pub created_by: Option<Uuid>,
+    pub code: String,
+    pub initial_status: MemberStatus,
+    pub max_uses: Option<i32>,
+    pub uses: i32,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<DbOrgInvite> for InviteResponse {
+    type Error = String;
+
+    fn try_from(invite: DbOrgInvite) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: invite.id,
+            discord_org_id: invite.discord_org_id,
+            created_by: invite.created_by,
+            code: invite.code,
+            initial_status: MemberStatus::try_from(invite.initial_status)?,
+            max_uses: invite.max_uses,
+            uses: invite.uses,
+            expires_at: invite.expires_at,
+            revoked_at: invite.revoked_at,
+            created_at: invite.created_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedemptionResponse {
+    pub id: Uuid,
+    pub invite_id: Uuid,
+    pub user: User,
+    pub member_id: Option<Uuid>, // None once the membership has been removed
+    pub redeemed_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<DbInviteRedemptionWithUser> for RedemptionResponse {
+    fn from(redemption: DbInviteRedemptionWithUser) -> Self {
+        Self {
+            id: redemption.id,
+            invite_id: redemption.invite_id,
+            user: User {
+                id: redemption.user_id,
+                discord_id: redemption.user_discord_id,
+                display_name: redemption.user_display_name,
+                avatar_url: redemption.user_avatar_url,
+                bio: redemption.user_bio,
+                created_at: redemption.user_created_at,
+                updated_at: redemption.user_updated_at,
+            },
+            member_id: redemption.member_id,
+            redeemed_at: redemption.redeemed_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInviteRequest {
+    pub initial_status: Option<MemberStatus>, // Defaults to Spectating
+    pub max_uses: Option<i32>,
+    pub expires_in: Option<i64>, // seconds from now
+}
+
+fn generate_invite_code() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(INVITE_CODE_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Create an invite code for an org (requires `MANAGE_INVITES`)
+pub async fn create_invite(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Json(request): Json<CreateInviteRequest>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_INVITES,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    let initial_status = request.initial_status.unwrap_or(MemberStatus::Spectating);
+    let error = if initial_status == MemberStatus::Banned {
+        Some("Invites can't grant the Banned status")
+    } else if request.max_uses.is_some_and(|max_uses| max_uses < 1) {
+        Some("max_uses must be at least 1")
+    } else {
+        None
+    };
+    if let Some(error) = error {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<InviteResponse>::error(error.to_string())),
+        )
+            .into_response();
+    }
+
+    let expires_at = match expires_at_from_now(request.expires_in, MAX_INVITE_TTL_SECONDS) {
+        Ok(expires_at) => expires_at,
+        Err(error) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::<InviteResponse>::error(error)),
+            )
+                .into_response();
+        }
+    };
+
+    let create_invite = CreateOrgInvite {
+        discord_org_id: org_id,
+        created_by: Some(auth.user_id),
+        code: generate_invite_code(),
+        initial_status,
+        max_uses: request.max_uses,
+        expires_at,
+    };
+
+    let invite = match queries::create_org_invite(&state.db_pool, create_invite).await {
+        Ok(db_invite) => InviteResponse::try_from(db_invite),
+        Err(e) => Err(format!("Failed to create invite: {}", e)),
+    };
+
+    match invite {
+        Ok(invite) => (StatusCode::CREATED, Json(ApiResponse::success(invite))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<InviteResponse>::error(e)),
+        )
+            .into_response(),
+    }
+}
+
+/// List an org's invites, including revoked, expired and used-up ones
+pub async fn list_invites(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_INVITES,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    let invites = match queries::get_org_invites_by_org(&state.db_pool, org_id).await {
+        Ok(db_invites) => db_invites
+            .into_iter()
+            .map(InviteResponse::try_from)
+            .collect::<Result<Vec<_>, _>>(),
+        Err(e) => Err(format!("Database error: {}", e)),
+    };
+
+    match invites {
+        Ok(invites) => (StatusCode::OK, Json(ApiResponse::success(invites))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<InviteResponse>>::error(e)),
+        )
+            .into_response(),
+    }
+}
+
+/// Revoke an org invite so it can no longer be redeemed
+pub async fn revoke_invite(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, invite_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_INVITES,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    match queries::revoke_org_invite(&state.db_pool, org_id, invite_id).await {
+        Ok(true) => (StatusCode::OK, Json(ApiResponse::success(()))).into_response(),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::error("Invite not found".to_string())),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to revoke invite: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// List who joined through an invite
+pub async fn list_invite_redemptions(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, invite_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_INVITES,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    match queries::get_invite_redemptions(&state.db_pool, org_id, invite_id).await {
+        Ok(db_redemptions) => {
+            let redemptions: Vec<RedemptionResponse> =
+                db_redemptions.into_iter().map(Into::into).collect();
+            (StatusCode::OK, Json(ApiResponse::success(redemptions))).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<RedemptionResponse>>::error(format!(
+                "Database error: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Join an org with an invite code, with the status the invite grants
+pub async fn redeem_invite(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(code): Path<String>,
+) -> Response {
+    let (status, error_msg) = match queries::redeem_org_invite(&state.db_pool, &code, auth.user_id)
+        .await
+    {
+        Ok(InviteRedemption::Redeemed(db_member)) => {
+            return load_member(&state.db_pool, db_member.id, StatusCode::CREATED).await;
+        }
+        Ok(InviteRedemption::NotFound) => (StatusCode::NOT_FOUND, "Invite not found".to_string()),
+        Ok(InviteRedemption::Expired) => (StatusCode::GONE, "This invite has expired".to_string()),
+        Ok(InviteRedemption::Exhausted) => (
+            StatusCode::GONE,
+            "This invite has reached its maximum number of uses".to_string(),
+        ),
+        Ok(InviteRedemption::Banned) => (
+            StatusCode::FORBIDDEN,
+            "You are banned from this organization".to_string(),
+        ),
+        Ok(InviteRedemption::AlreadyMember) => (
+            StatusCode::CONFLICT,
+            "You are already a member of this organization".to_string(),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to redeem invite: {}", e),
+        ),
+    };
+
+    (status, Json(ApiResponse::<Member>::error(error_msg))).into_response()
+}
@@ -0,0 +1,311 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::members::load_member;
+use super::users::ApiResponse;
+use crate::auth::AuthUser;
+use crate::auth::permissions;
+use crate::database::{DatabasePool, queries};
+use shared::database::{CreateJoinRequest, DbJoinRequest};
+use shared::models::{JoinRequest, JoinRequestStatus, Member, Permissions};
+
+#[derive(Debug, Deserialize)]
+pub struct RejectJoinRequestRequest {
+    pub reason: Option<String>,
+}
+
+fn join_request_response(
+    result: Result<DbJoinRequest, sqlx::Error>,
+    status: StatusCode,
+) -> Response {
+    let request = match result {
+        Ok(db_request) => JoinRequest::try_from(db_request),
+        Err(e) => Err(format!("Database error: {}", e)),
+    };
+
+    match request {
+        Ok(request) => (status, Json(ApiResponse::success(request))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<JoinRequest>::error(e)),
+        )
+            .into_response(),
+    }
+}
+
+fn join_requests_response(result: Result<Vec<DbJoinRequest>, sqlx::Error>) -> Response {
+    let requests = match result {
+        Ok(db_requests) => db_requests
+            .into_iter()
+            .map(JoinRequest::try_from)
+            .collect::<Result<Vec<_>, _>>(),
+        Err(e) => Err(format!("Database error: {}", e)),
+    };
+
+    match requests {
+        Ok(requests) => (StatusCode::OK, Json(ApiResponse::success(requests))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<JoinRequest>>::error(e)),
+        )
+            .into_response(),
+    }
+}
+
+/// Load one of an org's join requests that is still pending
+async fn pending_join_request(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    request_id: Uuid,
+) -> Result<DbJoinRequest, Response> {
+    let request = queries::get_join_request_by_id(pool, request_id)
+        .await
+        .map_err(|e| permissions::PermissionError::Database(e.to_string()).into_response())?
+        .filter(|request| request.discord_org_id == org_id)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::<JoinRequest>::error(
+                    "Join request not found".to_string(),
+                )),
+            )
+                .into_response()
+        })?;
+
+    if request.status != String::from(JoinRequestStatus::Pending) {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<JoinRequest>::error(
+                "This join request has already been handled".to_string(),
+            )),
+        )
+            .into_response());
+    }
+
+    Ok(request)
+}
+
+/// Create a pending join request for an org that requires approval
+pub(crate) async fn request_to_join(
+    pool: &DatabasePool,
+    user_id: Uuid,
+    org_id: Uuid,
+    message: Option<String>,
+) -> Response {
+    let error = match queries::get_member_by_user_and_org(pool, user_id, org_id).await {
+        Ok(Some(_)) => Some((
+            StatusCode::CONFLICT,
+            "You are already a member of this organization",
+        )),
+        Ok(None) => match queries::is_user_banned(pool, org_id, user_id).await {
+            Ok(true) => Some((
+                StatusCode::FORBIDDEN,
+                "You are banned from this organization",
+            )),
+            Ok(false) => None,
+            Err(e) => return permissions::PermissionError::Database(e.to_string()).into_response(),
+        },
+        Err(e) => return permissions::PermissionError::Database(e.to_string()).into_response(),
+    };
+    if let Some((status, error)) = error {
+        return (
+            status,
+            Json(ApiResponse::<JoinRequest>::error(error.to_string())),
+        )
+            .into_response();
+    }
+
+    let create_request = CreateJoinRequest {
+        discord_org_id: org_id,
+        user_id,
+        message: message.filter(|message| !message.trim().is_empty()),
+    };
+
+    match queries::create_join_request(pool, create_request).await {
+        Err(e) if e.to_string().contains("duplicate key") => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<JoinRequest>::error(
+                "You already have a pending request to join this organization".to_string(),
+            )),
+        )
+            .into_response(),
+        result => join_request_response(result, StatusCode::ACCEPTED),
+    }
+}
+
+/// List an org's pending join requests, oldest first (requires `MANAGE_MEMBERS`)
+pub async fn list_join_requests(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_MEMBERS,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    join_requests_response(queries::get_pending_join_requests_by_org(&state.db_pool, org_id).await)
+}
+
+/// Approve a join request, adding the applicant as a spectating member (requires
+/// `MANAGE_MEMBERS`)
+pub async fn approve_join_request(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, request_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_MEMBERS,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    if let Err(response) = pending_join_request(&state.db_pool, org_id, request_id).await {
+        return response;
+    }
+
+    let approval =
+        queries::approve_join_request(&state.db_pool, org_id, request_id, auth.user_id).await;
+    let db_member = match approval {
+        Ok(Some(db_member)) => db_member,
+        Ok(None) => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::<Member>::error(
+                    "This join request has already been handled".to_string(),
+                )),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            let (status, error_msg) = if queries::is_member_banned_error(&e) {
+                (
+                    StatusCode::FORBIDDEN,
+                    "This user is banned from this organization".to_string(),
+                )
+            } else if e.to_string().contains("duplicate key") {
+                (
+                    StatusCode::CONFLICT,
+                    "This user is already a member of this organization".to_string(),
+                )
+            } else {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Failed to add member: {}", e),
+                )
+            };
+            return (status, Json(ApiResponse::<Member>::error(error_msg))).into_response();
+        }
+    };
+
+    load_member(&state.db_pool, db_member.id, StatusCode::CREATED).await
+}
+
+/// Reject a join request with an optional reason shown to the applicant (requires
+/// `MANAGE_MEMBERS`)
+pub async fn reject_join_request(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, request_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<RejectJoinRequestRequest>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_MEMBERS,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    if let Err(response) = pending_join_request(&state.db_pool, org_id, request_id).await {
+        return response;
+    }
+
+    let reason = request
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+
+    match queries::decide_join_request(
+        &state.db_pool,
+        request_id,
+        JoinRequestStatus::Rejected,
+        auth.user_id,
+        reason,
+        None,
+    )
+    .await
+    {
+        Ok(Some(db_request)) => join_request_response(Ok(db_request), StatusCode::OK),
+        Ok(None) => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<JoinRequest>::error(
+                "This join request has already been handled".to_string(),
+            )),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<JoinRequest>::error(format!(
+                "Failed to reject join request: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// List the caller's join requests across orgs, newest first
+pub async fn list_my_join_requests(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+) -> Response {
+    join_requests_response(queries::get_join_requests_by_user(&state.db_pool, auth.user_id).await)
+}
+
+/// Withdraw one of the caller's pending join requests
+pub async fn withdraw_join_request(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(request_id): Path<Uuid>,
+) -> Response {
+    match queries::withdraw_join_request(&state.db_pool, request_id, auth.user_id).await {
+        Ok(true) => (StatusCode::NO_CONTENT, Json(ApiResponse::<()>::success(()))).into_response(),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::error(
+                "Pending join request not found".to_string(),
+            )),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to withdraw join request: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
@@ -0,0 +1,816 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use super::seasons::load_season;
+use super::users::ApiResponse;
+use crate::auth::permissions;
+use crate::auth::{AuthUser, Caller};
+use crate::config::Config;
+use crate::database::DatabasePool;
+use crate::database::queries::{self, MatchResponse};
+use shared::database::{
+    CreateMatch, CreateMatchParticipant, DbMatch, DbMatchParticipant, UpdateMatch,
+};
+use shared::models::{
+    ApiKeyScope, Match, MatchConfirmation, MatchStatus, MemberStatus, Permissions, User, UserStats,
+};
+
+const MAX_GAME_TYPE_LENGTH: usize = 64;
+const MAX_MATCH_PARTICIPANTS: usize = 64;
+
+#[derive(Debug, Deserialize)]
+pub struct RecordMatchRequest {
+    pub game_type: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub participants: Vec<CreateMatchParticipant>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListMatchesQuery {
+    pub game_type: Option<String>,
+    pub status: Option<MatchStatus>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DisputeMatchRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveMatchRequest {
+    pub outcome: MatchStatus, // `Finalized` or `Voided`
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserStatsQuery {
+    pub org_id: Option<Uuid>,
+    pub game_type: Option<String>,
+    pub season: Option<Uuid>, // Only matches that ended during the season
+}
+
+/// Trim and lowercase a game type so "Chess" and "chess " are counted together
+fn normalize_game_type(game_type: &str) -> Result<String, String> {
+    let game_type = game_type.trim().to_lowercase();
+
+    if game_type.is_empty() {
+        return Err("Game type can't be empty".to_string());
+    }
+    if game_type.chars().count() > MAX_GAME_TYPE_LENGTH {
+        return Err(format!(
+            "Game type can be at most {} characters long",
+            MAX_GAME_TYPE_LENGTH
+        ));
+    }
+
+    Ok(game_type)
+}
+
+/// Check a match's results: at least two distinct players, finite scores, and
+/// placements ranked like "1, 2, 2, 4" where tied players share a placement and the
+/// next placement skips past them
+fn validate_participants(participants: &[CreateMatchParticipant]) -> Result<(), String> {
+    if participants.len() < 2 {
+        return Err("A match needs at least two participants".to_string());
+    }
+    if participants.len() > MAX_MATCH_PARTICIPANTS {
+        return Err(format!(
+            "A match can have at most {} participants",
+            MAX_MATCH_PARTICIPANTS
+        ));
+    }
+
+    let mut seen = HashSet::with_capacity(participants.len());
+    if !participants
+        .iter()
+        .all(|participant| seen.insert(participant.user_id))
+    {
+        return Err("Each user can only appear once in a match".to_string());
+    }
+
+    if participants
+        .iter()
+        .any(|participant| participant.score.is_some_and(|score| !score.is_finite()))
+    {
+        return Err("Scores must be finite numbers".to_string());
+    }
+
+    let mut placements: Vec<i32> = participants
+        .iter()
+        .map(|participant| participant.placement)
+        .collect();
+    placements.sort_unstable();
+
+    for (index, placement) in placements.iter().enumerate() {
+        let expected = index as i32 + 1;
+        if *placement != expected && (index == 0 || placements[index - 1] != *placement) {
+            return Err(format!(
+                "Placements must start at 1 and only skip past ties (expected {}, got {})",
+                expected, placement
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_times(started_at: DateTime<Utc>, ended_at: DateTime<Utc>) -> Result<(), String> {
+    if ended_at < started_at {
+        return Err("A match can't end before it starts".to_string());
+    }
+    if ended_at > Utc::now() {
+        return Err("A match can't end in the future".to_string());
+    }
+
+    Ok(())
+}
+
+fn bad_request(error: String) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ApiResponse::<Match>::error(error)),
+    )
+        .into_response()
+}
+
+/// Require every participant to be a member of the org who isn't banned
+async fn require_participants_are_members(
+    pool: &DatabasePool,
+    org_id: Uuid,
+    participants: &[CreateMatchParticipant],
+) -> Result<(), Response> {
+    for participant in participants {
+        let member = queries::get_member_by_user_and_org(pool, participant.user_id, org_id)
+            .await
+            .map_err(|e| permissions::PermissionError::Database(e.to_string()).into_response())?;
+
+        if member.is_none_or(|member| member.status == String::from(MemberStatus::Banned)) {
+            return Err(bad_request(format!(
+                "User {} is not a member of this organization",
+                participant.user_id
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Load one of an org's matches
+async fn org_match(pool: &DatabasePool, org_id: Uuid, match_id: Uuid) -> Result<DbMatch, Response> {
+    queries::get_match_by_id(pool, match_id)
+        .await
+        .map_err(|e| permissions::PermissionError::Database(e.to_string()).into_response())?
+        .filter(|db_match| db_match.discord_org_id == org_id)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::<Match>::error("Match not found".to_string())),
+            )
+                .into_response()
+        })
+}
+
+/// Attach each match's results, keeping the matches' order
+async fn with_participants(
+    pool: &DatabasePool,
+    db_matches: Vec<DbMatch>,
+) -> Result<Vec<Match>, String> {
+    let match_ids: Vec<Uuid> = db_matches.iter().map(|db_match| db_match.id).collect();
+
+    let mut participants: HashMap<Uuid, Vec<DbMatchParticipant>> = HashMap::new();
+    for participant in queries::get_match_participants(pool, &match_ids)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?
+    {
+        participants
+            .entry(participant.match_id)
+            .or_default()
+            .push(participant);
+    }
+
+    db_matches
+        .into_iter()
+        .map(|db_match| {
+            let results = participants.remove(&db_match.id).unwrap_or_default();
+            Match::try_from((db_match, results))
+        })
+        .collect()
+}
+
+fn match_response(result: (DbMatch, Vec<DbMatchParticipant>), status: StatusCode) -> Response {
+    match Match::try_from(result) {
+        Ok(result) => (status, Json(ApiResponse::success(result))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Match>::error(e)),
+        )
+            .into_response(),
+    }
+}
+
+/// Load a match's results and return it
+async fn load_match(pool: &DatabasePool, db_match: DbMatch) -> Response {
+    match with_participants(pool, vec![db_match]).await {
+        Ok(mut matches) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(matches.remove(0))),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Match>::error(e)),
+        )
+            .into_response(),
+    }
+}
+
+fn already_settled() -> Response {
+    (
+        StatusCode::CONFLICT,
+        Json(ApiResponse::<Match>::error(
+            "This match has already been settled".to_string(),
+        )),
+    )
+        .into_response()
+}
+
+/// Report a finished match in an org (users need to be members, API keys need the
+/// `matches:write` scope); every participant has to be a member too. The other
+/// participants then have until the confirmation deadline to confirm or dispute it.
+pub async fn record_match(
+    State(state): State<crate::AppState>,
+    caller: Caller,
+    Path(org_id): Path<Uuid>,
+    Json(request): Json<RecordMatchRequest>,
+) -> Response {
+    if let Err(e) = permissions::require_caller_access(
+        &state.db_pool,
+        &caller,
+        org_id,
+        Permissions::VIEW_MEMBERS,
+        ApiKeyScope::MatchesWrite,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    let game_type = match normalize_game_type(&request.game_type) {
+        Ok(game_type) => game_type,
+        Err(e) => return bad_request(e),
+    };
+    if let Err(e) = validate_times(request.started_at, request.ended_at)
+        .and_then(|_| validate_participants(&request.participants))
+    {
+        return bad_request(e);
+    }
+    if let Err(response) =
+        require_participants_are_members(&state.db_pool, org_id, &request.participants).await
+    {
+        return response;
+    }
+
+    let create_match = CreateMatch {
+        discord_org_id: org_id,
+        game_type,
+        reported_by: match &caller {
+            Caller::User(auth) => Some(auth.user_id),
+            Caller::ApiKey(_) => None,
+        },
+        started_at: request.started_at,
+        ended_at: request.ended_at,
+        confirm_deadline: Utc::now()
+            + chrono::Duration::seconds(Config::match_confirmation_window_seconds()),
+        participants: request.participants,
+    };
+
+    match queries::create_match(&state.db_pool, create_match).await {
+        Ok(result) => match_response(result, StatusCode::CREATED),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Match>::error(format!(
+                "Failed to record match: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// List an org's matches, most recent first, optionally filtered with
+/// `?game_type=chess&status=Finalized` (users need `VIEW_MEMBERS`, API keys the
+/// `leaderboard:read` scope)
+pub async fn list_matches(
+    State(state): State<crate::AppState>,
+    caller: Caller,
+    Path(org_id): Path<Uuid>,
+    Query(params): Query<ListMatchesQuery>,
+) -> Response {
+    if let Err(e) = permissions::require_caller_access(
+        &state.db_pool,
+        &caller,
+        org_id,
+        Permissions::VIEW_MEMBERS,
+        ApiKeyScope::LeaderboardRead,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    let limit = params.limit.unwrap_or(20).clamp(1, 100); // Max 100 items per page
+    let offset = params.offset.unwrap_or(0).max(0); // Ensure non-negative
+    let game_type = params
+        .game_type
+        .map(|game_type| game_type.trim().to_lowercase())
+        .filter(|game_type| !game_type.is_empty());
+
+    let matches = match queries::get_matches_by_org(
+        &state.db_pool,
+        org_id,
+        game_type.as_deref(),
+        params.status,
+        limit,
+        offset,
+    )
+    .await
+    {
+        Ok(db_matches) => with_participants(&state.db_pool, db_matches).await,
+        Err(e) => Err(format!("Database error: {}", e)),
+    };
+
+    match matches {
+        Ok(matches) => (StatusCode::OK, Json(ApiResponse::success(matches))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<Match>>::error(e)),
+        )
+            .into_response(),
+    }
+}
+
+/// Get one of an org's matches with its results (users need `VIEW_MEMBERS`, API keys
+/// the `leaderboard:read` scope)
+pub async fn get_match(
+    State(state): State<crate::AppState>,
+    caller: Caller,
+    Path((org_id, match_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(e) = permissions::require_caller_access(
+        &state.db_pool,
+        &caller,
+        org_id,
+        Permissions::VIEW_MEMBERS,
+        ApiKeyScope::LeaderboardRead,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    match org_match(&state.db_pool, org_id, match_id).await {
+        Ok(db_match) => load_match(&state.db_pool, db_match).await,
+        Err(response) => response,
+    }
+}
+
+/// Correct a pending or disputed match's details or results (requires
+/// `MANAGE_MEMBERS`); `participants` replaces every result, which then has to be
+/// confirmed again within a fresh confirmation window
+pub async fn update_match(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, match_id)): Path<(Uuid, Uuid)>,
+    Json(mut update_match): Json<UpdateMatch>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_MEMBERS,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    let db_match = match org_match(&state.db_pool, org_id, match_id).await {
+        Ok(db_match) => db_match,
+        Err(response) => return response,
+    };
+    if !matches!(
+        MatchStatus::try_from(db_match.status.clone()),
+        Ok(MatchStatus::Pending | MatchStatus::Disputed)
+    ) {
+        return already_settled();
+    }
+
+    if let Some(game_type) = update_match.game_type.take() {
+        match normalize_game_type(&game_type) {
+            Ok(game_type) => update_match.game_type = Some(game_type),
+            Err(e) => return bad_request(e),
+        }
+    }
+    if let Err(e) = validate_times(
+        update_match.started_at.unwrap_or(db_match.started_at),
+        update_match.ended_at.unwrap_or(db_match.ended_at),
+    ) {
+        return bad_request(e);
+    }
+    if let Some(participants) = &update_match.participants {
+        if let Err(e) = validate_participants(participants) {
+            return bad_request(e);
+        }
+        if let Err(response) =
+            require_participants_are_members(&state.db_pool, org_id, participants).await
+        {
+            return response;
+        }
+    }
+
+    let confirm_deadline =
+        Utc::now() + chrono::Duration::seconds(Config::match_confirmation_window_seconds());
+
+    match queries::update_match(&state.db_pool, match_id, update_match, confirm_deadline).await {
+        Ok(Some(result)) => match_response(result, StatusCode::OK),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<Match>::error("Match not found".to_string())),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Match>::error(format!(
+                "Failed to update match: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Delete a match and its results (requires `MANAGE_MEMBERS`); ratings are rebuilt when
+/// the match was finalized
+pub async fn delete_match(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, match_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_MEMBERS,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    if let Err(response) = org_match(&state.db_pool, org_id, match_id).await {
+        return response;
+    }
+
+    match queries::delete_match(&state.db_pool, match_id).await {
+        Ok(true) => (StatusCode::NO_CONTENT, Json(ApiResponse::<()>::success(()))).into_response(),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::error("Match not found".to_string())),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to delete match: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Turn a participant's confirmation or dispute into a response
+async fn confirmation_response(pool: &DatabasePool, outcome: MatchResponse) -> Response {
+    let (status, error) = match outcome {
+        MatchResponse::Recorded(db_match) => return load_match(pool, *db_match).await,
+        MatchResponse::NotFound => (StatusCode::NOT_FOUND, "Match not found"),
+        MatchResponse::NotParticipant => (
+            StatusCode::FORBIDDEN,
+            "Only the match's participants can confirm or dispute it",
+        ),
+        MatchResponse::NotPending => return already_settled(),
+        MatchResponse::WindowClosed => (
+            StatusCode::CONFLICT,
+            "The window to confirm or dispute this match has closed",
+        ),
+    };
+
+    (status, Json(ApiResponse::<Match>::error(error.to_string()))).into_response()
+}
+
+/// Confirm the reported result of a match the caller played in; the last confirmation
+/// finalizes it
+pub async fn confirm_match(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, match_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(response) = org_match(&state.db_pool, org_id, match_id).await {
+        return response;
+    }
+
+    match queries::respond_to_match(
+        &state.db_pool,
+        match_id,
+        auth.user_id,
+        MatchConfirmation::Confirmed,
+        None,
+    )
+    .await
+    {
+        Ok(outcome) => confirmation_response(&state.db_pool, outcome).await,
+        Err(e) => permissions::PermissionError::Database(e.to_string()).into_response(),
+    }
+}
+
+/// Dispute the reported result of a match the caller played in, sending it to the
+/// org's dispute queue
+pub async fn dispute_match(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, match_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<DisputeMatchRequest>,
+) -> Response {
+    let reason = request.reason.trim();
+    if reason.is_empty() {
+        return bad_request("Say what's wrong with the reported result".to_string());
+    }
+
+    if let Err(response) = org_match(&state.db_pool, org_id, match_id).await {
+        return response;
+    }
+
+    match queries::respond_to_match(
+        &state.db_pool,
+        match_id,
+        auth.user_id,
+        MatchConfirmation::Disputed,
+        Some(reason),
+    )
+    .await
+    {
+        Ok(outcome) => confirmation_response(&state.db_pool, outcome).await,
+        Err(e) => permissions::PermissionError::Database(e.to_string()).into_response(),
+    }
+}
+
+/// List an org's disputed matches waiting for an admin (requires `MANAGE_MEMBERS`)
+pub async fn list_disputed_matches(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_MEMBERS,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    let matches = match queries::get_matches_by_org(
+        &state.db_pool,
+        org_id,
+        None,
+        Some(MatchStatus::Disputed),
+        100,
+        0,
+    )
+    .await
+    {
+        Ok(db_matches) => with_participants(&state.db_pool, db_matches).await,
+        Err(e) => Err(format!("Database error: {}", e)),
+    };
+
+    match matches {
+        Ok(matches) => (StatusCode::OK, Json(ApiResponse::success(matches))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<Match>>::error(e)),
+        )
+            .into_response(),
+    }
+}
+
+/// Settle a disputed match as `Finalized` or `Voided` (requires `MANAGE_MEMBERS`);
+/// correct its results first to finalize different placements
+pub async fn resolve_match(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, match_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<ResolveMatchRequest>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_MEMBERS,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    if !matches!(
+        request.outcome,
+        MatchStatus::Finalized | MatchStatus::Voided
+    ) {
+        return bad_request("A dispute can only be resolved as Finalized or Voided".to_string());
+    }
+
+    if let Err(response) = org_match(&state.db_pool, org_id, match_id).await {
+        return response;
+    }
+
+    let note = request
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|note| !note.is_empty());
+
+    match queries::resolve_disputed_match(
+        &state.db_pool,
+        match_id,
+        request.outcome,
+        auth.user_id,
+        note,
+    )
+    .await
+    {
+        Ok(Some(db_match)) => load_match(&state.db_pool, db_match).await,
+        Ok(None) => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<Match>::error(
+                "Only disputed matches can be resolved".to_string(),
+            )),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Match>::error(format!(
+                "Failed to resolve match: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Get a user's stats from their finalized matches, optionally narrowed with
+/// `?org_id=...&game_type=chess&season=...`. Users need `VIEW_MEMBERS` in the org (and
+/// only see orgs they belong to without one); API keys need the `leaderboard:read` scope
+/// and are limited to their own org.
+pub async fn get_user_stats(
+    State(state): State<crate::AppState>,
+    caller: Caller,
+    Path(user_id): Path<Uuid>,
+    Query(params): Query<UserStatsQuery>,
+) -> Response {
+    let user: User = match queries::get_user_by_id(&state.db_pool, user_id).await {
+        Ok(Some(db_user)) => db_user.into(),
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::<UserStats>::error(
+                    "User not found".to_string(),
+                )),
+            )
+                .into_response();
+        }
+        Err(e) => return permissions::PermissionError::Database(e.to_string()).into_response(),
+    };
+
+    let game_type = params
+        .game_type
+        .map(|game_type| game_type.trim().to_lowercase())
+        .filter(|game_type| !game_type.is_empty());
+
+    // A season narrows the stats to its org and dates
+    let (org_id, period) = match params.season {
+        Some(season_id) => match load_season(&state.db_pool, params.org_id, season_id).await {
+            Ok(season) => (
+                Some(season.discord_org_id),
+                Some((season.starts_at, season.ends_at)),
+            ),
+            Err(response) => return response,
+        },
+        None => (params.org_id, None),
+    };
+
+    let org_id = match (&caller, org_id) {
+        (Caller::ApiKey(key), None) => Some(key.org_id),
+        (_, org_id) => org_id,
+    };
+    if let Some(org_id) = org_id
+        && let Err(e) = permissions::require_caller_access(
+            &state.db_pool,
+            &caller,
+            org_id,
+            Permissions::VIEW_MEMBERS,
+            ApiKeyScope::LeaderboardRead,
+        )
+        .await
+    {
+        return e.into_response();
+    }
+
+    // Without an org, only count the orgs the caller can see
+    let viewer_id = match &caller {
+        Caller::User(auth) if org_id.is_none() && !auth.is_admin => Some(auth.user_id),
+        _ => None,
+    };
+
+    match queries::get_user_match_stats(
+        &state.db_pool,
+        user_id,
+        org_id,
+        game_type.as_deref(),
+        period,
+        viewer_id,
+    )
+    .await
+    {
+        Ok((total_games, average_placement)) => {
+            let stats = UserStats {
+                user,
+                total_games: i32::try_from(total_games).unwrap_or(i32::MAX),
+                average_placement: average_placement.unwrap_or(0.0), // 0 until the first game
+            };
+            (StatusCode::OK, Json(ApiResponse::success(stats))).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<UserStats>::error(format!(
+                "Failed to get user stats: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn participants(placements: &[i32]) -> Vec<CreateMatchParticipant> {
+        placements
+            .iter()
+            .map(|&placement| CreateMatchParticipant {
+                user_id: Uuid::new_v4(),
+                placement,
+                score: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_normalize_game_type() {
+        assert_eq!(normalize_game_type(" Chess "), Ok("chess".to_string()));
+        assert!(normalize_game_type("   ").is_err());
+        assert!(normalize_game_type(&"x".repeat(MAX_GAME_TYPE_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_validate_participants() {
+        assert!(validate_participants(&participants(&[2, 1, 3])).is_ok());
+        assert!(validate_participants(&participants(&[1, 2, 2, 4])).is_ok());
+        assert!(validate_participants(&participants(&[1, 1])).is_ok());
+        assert!(validate_participants(&participants(&[1])).is_err());
+        assert!(validate_participants(&participants(&[0, 1])).is_err());
+        assert!(validate_participants(&participants(&[1, 3])).is_err());
+        assert!(validate_participants(&participants(&[1, 2, 2, 3])).is_err());
+
+        let mut duplicated = participants(&[1, 2]);
+        duplicated[1].user_id = duplicated[0].user_id;
+        assert!(validate_participants(&duplicated).is_err());
+
+        let mut nan_score = participants(&[1, 2]);
+        nan_score[0].score = Some(f64::NAN);
+        assert!(validate_participants(&nan_score).is_err());
+    }
+}
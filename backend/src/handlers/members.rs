@@ -0,0 +1,567 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::expires_at_from_now;
+use super::join_requests;
+use super::users::ApiResponse;
+use crate::auth::permissions::{self, OrgAccess};
+use crate::auth::{AuthUser, Caller};
+use crate::database::DatabasePool;
+use crate::database::queries::{self, StatusChange};
+use shared::database::{CreateMember, CreateMemberBan, DbMemberWithRelations, UpdateMember};
+use shared::models::{
+    ApiKeyScope, Member, MemberBan, MemberStatus, MemberStatusChange, OrgRole, Permissions,
+};
+
+const MAX_BAN_DURATION_SECONDS: i64 = 10 * 365 * 24 * 60 * 60;
+
+#[derive(Debug, Deserialize)]
+pub struct ListMembersQuery {
+    pub status: Option<MemberStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JoinOrgRequest {
+    pub message: Option<String>, // Shown to admins when the org requires approval
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMemberRoleRequest {
+    pub role: OrgRole,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMemberStatusRequest {
+    pub status: MemberStatus,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BanMemberRequest {
+    pub reason: Option<String>,
+    pub expires_in: Option<i64>, // seconds from now; permanent when omitted
+}
+
+/// Load a member with its user and org for API responses
+pub(crate) async fn load_member(
+    pool: &DatabasePool,
+    member_id: Uuid,
+    status: StatusCode,
+) -> Response {
+    match queries::get_member_with_relations(pool, member_id).await {
+        Ok(Some(db_member)) => match Member::try_from(db_member) {
+            Ok(member) => (status, Json(ApiResponse::success(member))).into_response(),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<Member>::error(e)),
+            )
+                .into_response(),
+        },
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<Member>::error("Member not found".to_string())),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Member>::error(format!(
+                "Database error: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// List an org's members, optionally filtered with `?status=Playing` (users need
+/// `VIEW_MEMBERS`, API keys the `members:read` scope)
+pub async fn list_org_members(
+    State(state): State<crate::AppState>,
+    caller: Caller,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<ListMembersQuery>,
+) -> Response {
+    if let Err(e) = permissions::require_caller_access(
+        &state.db_pool,
+        &caller,
+        org_id,
+        Permissions::VIEW_MEMBERS,
+        ApiKeyScope::MembersRead,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    members_response(queries::get_members_by_org(&state.db_pool, org_id, query.status).await)
+}
+
+/// List a user's memberships across orgs (self or admin)
+pub async fn list_user_memberships(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(user_id): Path<Uuid>,
+) -> Response {
+    if !auth.can_act_for(user_id) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<Vec<Member>>::error(
+                "You can only view your own memberships".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    members_response(queries::get_members_by_user(&state.db_pool, user_id).await)
+}
+
+/// Join an org as a spectating member, or ask to join with an optional message when
+/// the org requires approval
+pub async fn join_org(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    request: Option<Json<JoinOrgRequest>>,
+) -> Response {
+    let org = match queries::get_discord_org_by_id(&state.db_pool, org_id).await {
+        Ok(Some(org)) => org,
+        Ok(None) => return permissions::PermissionError::OrgNotFound.into_response(),
+        Err(e) => return permissions::PermissionError::Database(e.to_string()).into_response(),
+    };
+
+    if org.requires_approval {
+        let message = request.and_then(|Json(request)| request.message);
+        return join_requests::request_to_join(&state.db_pool, auth.user_id, org_id, message).await;
+    }
+
+    let create_member = CreateMember {
+        user_id: auth.user_id,
+        discord_org_id: org_id,
+        status: MemberStatus::Spectating,
+        role: OrgRole::Member,
+    };
+
+    match queries::create_member(&state.db_pool, create_member).await {
+        Ok(db_member) => load_member(&state.db_pool, db_member.id, StatusCode::CREATED).await,
+        Err(e) => {
+            let (status, error_msg) = if queries::is_member_banned_error(&e) {
+                (
+                    StatusCode::FORBIDDEN,
+                    "You are banned from this organization".to_string(),
+                )
+            } else if e.to_string().contains("duplicate key") {
+                (
+                    StatusCode::CONFLICT,
+                    "You are already a member of this organization".to_string(),
+                )
+            } else {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Failed to join organization: {}", e),
+                )
+            };
+            (status, Json(ApiResponse::<Member>::error(error_msg))).into_response()
+        }
+    }
+}
+
+/// Leave an org. Owners must transfer ownership first, and banned members stay on
+/// record so the ban keeps applying.
+pub async fn leave_org(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    let member =
+        match queries::get_member_by_user_and_org(&state.db_pool, auth.user_id, org_id).await {
+            Ok(Some(member)) => member,
+            Ok(None) => return permissions::PermissionError::MemberNotFound.into_response(),
+            Err(e) => return permissions::PermissionError::Database(e.to_string()).into_response(),
+        };
+
+    if member.role == String::from(OrgRole::Owner) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<()>::error(
+                "The owner can't leave the organization; transfer ownership first".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    if member.status == String::from(MemberStatus::Banned) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<()>::error(
+                "Banned members can't leave the organization".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    match queries::delete_member(&state.db_pool, member.id).await {
+        Ok(true) => (StatusCode::NO_CONTENT, Json(ApiResponse::<()>::success(()))).into_response(),
+        Ok(false) => permissions::PermissionError::MemberNotFound.into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to leave organization: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+fn members_response(result: Result<Vec<DbMemberWithRelations>, sqlx::Error>) -> Response {
+    let members = match result {
+        Ok(db_members) => db_members
+            .into_iter()
+            .map(Member::try_from)
+            .collect::<Result<Vec<_>, _>>(),
+        Err(e) => Err(format!("Database error: {}", e)),
+    };
+
+    match members {
+        Ok(members) => (StatusCode::OK, Json(ApiResponse::success(members))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<Member>>::error(e)),
+        )
+            .into_response(),
+    }
+}
+
+/// Get the caller's role and permissions in an org
+pub async fn get_org_permissions(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    match permissions::org_access(&state.db_pool, &auth, org_id).await {
+        Ok(access) => (
+            StatusCode::OK,
+            Json(ApiResponse::<OrgAccess>::success(access)),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Change a member's org role (requires `MANAGE_ROLES` and a higher role than both the
+/// member's current and new role)
+pub async fn update_member_role(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, member_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateMemberRoleRequest>,
+) -> Response {
+    let access = match permissions::require_member_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        member_id,
+        Permissions::MANAGE_ROLES,
+    )
+    .await
+    {
+        Ok((access, _)) => access,
+        Err(e) => return e.into_response(),
+    };
+
+    if request.role == OrgRole::Owner {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<Member>::error(
+                "The owner role can only be transferred".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    if !access.outranks(request.role) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<Member>::error(
+                "You can only grant roles lower than your own".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    let update_member = UpdateMember {
+        status: None,
+        role: Some(request.role),
+    };
+
+    match queries::update_member(&state.db_pool, member_id, update_member).await {
+        Ok(Some(_)) => load_member(&state.db_pool, member_id, StatusCode::OK).await,
+        Ok(None) => permissions::PermissionError::MemberNotFound.into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Member>::error(format!(
+                "Failed to update member: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Change a member's status between `Spectating` and `Playing` (requires `MANAGE_MEMBERS`);
+/// bans go through the ban endpoints
+pub async fn update_member_status(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, member_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateMemberStatusRequest>,
+) -> Response {
+    if let Err(e) = permissions::require_member_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        member_id,
+        Permissions::MANAGE_MEMBERS,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    if request.status == MemberStatus::Banned {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<Member>::error(
+                "Use the ban endpoint to ban a member".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    let reason = request
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+
+    match queries::change_member_status(
+        &state.db_pool,
+        member_id,
+        request.status.clone(),
+        Some(auth.user_id),
+        reason,
+    )
+    .await
+    {
+        Ok(StatusChange::Changed(member)) => {
+            load_member(&state.db_pool, member.id, StatusCode::OK).await
+        }
+        Ok(StatusChange::NotFound) => permissions::PermissionError::MemberNotFound.into_response(),
+        Ok(StatusChange::NotAllowed(MemberStatus::Banned)) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<Member>::error(
+                "Banned members have to be unbanned first (DELETE .../members/:member_id/ban)"
+                    .to_string(),
+            )),
+        )
+            .into_response(),
+        Ok(StatusChange::NotAllowed(current)) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<Member>::error(format!(
+                "A {:?} member can't be moved to {:?}",
+                current, request.status
+            ))),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Member>::error(format!(
+                "Failed to update member: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// List a member's status changes, oldest first (requires `MANAGE_MEMBERS`)
+pub async fn get_member_status_history(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, member_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::MANAGE_MEMBERS,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    match queries::get_member_by_id(&state.db_pool, member_id).await {
+        Ok(Some(member)) if member.discord_org_id == org_id => {}
+        Ok(_) => return permissions::PermissionError::MemberNotFound.into_response(),
+        Err(e) => return permissions::PermissionError::Database(e.to_string()).into_response(),
+    }
+
+    let history = match queries::get_member_status_history(&state.db_pool, member_id).await {
+        Ok(db_history) => db_history
+            .into_iter()
+            .map(MemberStatusChange::try_from)
+            .collect::<Result<Vec<_>, _>>(),
+        Err(e) => Err(format!("Database error: {}", e)),
+    };
+
+    match history {
+        Ok(history) => (StatusCode::OK, Json(ApiResponse::success(history))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<MemberStatusChange>>::error(e)),
+        )
+            .into_response(),
+    }
+}
+
+/// Ban a member, optionally until a set time (requires `BAN_MEMBERS`); banning an
+/// already banned member replaces their ban
+pub async fn ban_member(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, member_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<BanMemberRequest>,
+) -> Response {
+    let member = match permissions::require_member_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        member_id,
+        Permissions::BAN_MEMBERS,
+    )
+    .await
+    {
+        Ok((_, member)) => member,
+        Err(e) => return e.into_response(),
+    };
+
+    if member.role == String::from(OrgRole::Owner) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<MemberBan>::error(
+                "The owner can't be banned".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    let expires_at = match expires_at_from_now(request.expires_in, MAX_BAN_DURATION_SECONDS) {
+        Ok(expires_at) => expires_at,
+        Err(error) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::<MemberBan>::error(error)),
+            )
+                .into_response();
+        }
+    };
+
+    let create_ban = CreateMemberBan {
+        discord_org_id: org_id,
+        user_id: member.user_id,
+        banned_by: Some(auth.user_id),
+        reason: request.reason.filter(|reason| !reason.trim().is_empty()),
+        expires_at,
+    };
+
+    match queries::ban_member(&state.db_pool, create_ban).await {
+        Ok(db_ban) => (
+            StatusCode::CREATED,
+            Json(ApiResponse::success(MemberBan::from(db_ban))),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<MemberBan>::error(format!(
+                "Failed to ban member: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Lift a member's ban and restore them to `Spectating` (requires `BAN_MEMBERS`)
+pub async fn unban_member(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, member_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    let member = match permissions::require_member_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        member_id,
+        Permissions::BAN_MEMBERS,
+    )
+    .await
+    {
+        Ok((_, member)) => member,
+        Err(e) => return e.into_response(),
+    };
+
+    match queries::unban_member(&state.db_pool, org_id, member.user_id, auth.user_id).await {
+        Ok(true) => load_member(&state.db_pool, member_id, StatusCode::OK).await,
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<Member>::error(
+                "This member isn't banned".to_string(),
+            )),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Member>::error(format!(
+                "Failed to unban member: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// List an org's open bans (requires `BAN_MEMBERS`)
+pub async fn list_bans(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    if let Err(e) =
+        permissions::require_org_permission(&state.db_pool, &auth, org_id, Permissions::BAN_MEMBERS)
+            .await
+    {
+        return e.into_response();
+    }
+
+    match queries::get_open_bans_by_org(&state.db_pool, org_id).await {
+        Ok(db_bans) => {
+            let bans: Vec<MemberBan> = db_bans.into_iter().map(Into::into).collect();
+            (StatusCode::OK, Json(ApiResponse::success(bans))).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<MemberBan>>::error(format!(
+                "Database error: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
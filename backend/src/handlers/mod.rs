@@ -1,8 +1,62 @@
+pub mod api_keys;
 pub mod discord_tokens;
+pub mod guilds;
+pub mod invites;
+pub mod join_requests;
+pub mod matches;
+pub mod members;
+pub mod orgs;
+pub mod ownership_transfers;
+pub mod ratings;
+pub mod seasons;
+pub mod sessions;
 pub mod users;
 
+use chrono::{DateTime, TimeDelta, Utc};
+
 // Re-export handler functions without conflicts
 pub use users::{
     create_user, delete_user, get_user, get_user_by_discord_id, get_user_stats, list_users,
     update_user,
 };
+
+/// Turn a request's `expires_in` (seconds from now) into an expiry time, rejecting values
+/// below one second or above `max_seconds`
+pub(crate) fn expires_at_from_now(
+    expires_in: Option<i64>,
+    max_seconds: i64,
+) -> Result<Option<DateTime<Utc>>, String> {
+    let Some(seconds) = expires_in else {
+        return Ok(None);
+    };
+    if !(1..=max_seconds).contains(&seconds) {
+        return Err(format!(
+            "expires_in must be between 1 and {} seconds",
+            max_seconds
+        ));
+    }
+
+    TimeDelta::try_seconds(seconds)
+        .and_then(|delta| Utc::now().checked_add_signed(delta))
+        .map(Some)
+        .ok_or_else(|| "expires_in is out of range".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expires_at_from_now() {
+        let now = Utc::now();
+        let expires_at = expires_at_from_now(Some(60), 3600).unwrap().unwrap();
+        assert!(expires_at >= now + TimeDelta::seconds(60));
+
+        assert_eq!(expires_at_from_now(None, 3600), Ok(None));
+        assert!(expires_at_from_now(Some(3600), 3600).unwrap().is_some());
+        assert!(expires_at_from_now(Some(0), 3600).is_err());
+        assert!(expires_at_from_now(Some(-60), 3600).is_err());
+        assert!(expires_at_from_now(Some(3601), 3600).is_err());
+        assert!(expires_at_from_now(Some(i64::MAX), i64::MAX).is_err());
+    }
+}
@@ -0,0 +1,433 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::users::ApiResponse;
+use crate::auth::AuthUser;
+use crate::auth::permissions;
+use crate::database::{DatabasePool, queries};
+use crate::tasks;
+use shared::database::{CreateDiscordOrg, DbDiscordOrg, UpdateDiscordOrg};
+use shared::models::{DiscordOrg, OrgListing, Permissions, User};
+
+const MAX_ORG_TAGS: usize = 10;
+const MAX_ORG_TAG_LENGTH: usize = 32;
+
+#[derive(Deserialize)]
+pub struct ListOrgsQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiscoverSort {
+    #[default]
+    Relevance,
+    Members,
+    Newest,
+}
+
+impl DiscoverSort {
+    fn as_str(self) -> &'static str {
+        match self {
+            DiscoverSort::Relevance => "relevance",
+            DiscoverSort::Members => "members",
+            DiscoverSort::Newest => "newest",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct DiscoverOrgsQuery {
+    pub q: Option<String>,
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub sort: DiscoverSort,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateOrgRequest {
+    pub name: String,
+    pub avatar_url: Option<String>,
+    pub description: Option<String>,
+    pub requires_approval: Option<bool>, // Defaults to false
+    pub listed: Option<bool>,            // Defaults to false
+    pub tags: Option<Vec<String>>,
+}
+
+/// Trim, lowercase and dedupe org tags, rejecting empty, overlong or too many tags
+fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() {
+            return Err("Tags can't be empty".to_string());
+        }
+        if tag.chars().count() > MAX_ORG_TAG_LENGTH {
+            return Err(format!(
+                "Tags can be at most {} characters long",
+                MAX_ORG_TAG_LENGTH
+            ));
+        }
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+
+    if normalized.len() > MAX_ORG_TAGS {
+        return Err(format!("An org can have at most {} tags", MAX_ORG_TAGS));
+    }
+
+    Ok(normalized)
+}
+
+/// Attach the owner `User` to an org row
+pub(crate) async fn org_with_owner(
+    pool: &DatabasePool,
+    db_org: DbDiscordOrg,
+) -> Result<DiscordOrg, sqlx::Error> {
+    let owner = queries::get_user_by_id(pool, db_org.owner_id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+    Ok(db_org.into_org(owner.into()))
+}
+
+/// Attach owners to a list of org rows, loading each distinct owner once
+pub(crate) async fn orgs_with_owners(
+    pool: &DatabasePool,
+    db_orgs: Vec<DbDiscordOrg>,
+) -> Result<Vec<DiscordOrg>, sqlx::Error> {
+    let mut owners: HashMap<Uuid, User> = HashMap::new();
+    let mut orgs = Vec::with_capacity(db_orgs.len());
+
+    for db_org in db_orgs {
+        let owner = match owners.get(&db_org.owner_id) {
+            Some(owner) => owner.clone(),
+            None => {
+                let owner: User = queries::get_user_by_id(pool, db_org.owner_id)
+                    .await?
+                    .ok_or(sqlx::Error::RowNotFound)?
+                    .into();
+                owners.insert(owner.id, owner.clone());
+                owner
+            }
+        };
+        orgs.push(db_org.into_org(owner));
+    }
+
+    Ok(orgs)
+}
+
+/// Respond with a single org and its owner
+pub(crate) async fn org_response(
+    pool: &DatabasePool,
+    db_org: DbDiscordOrg,
+    status: StatusCode,
+) -> Response {
+    match org_with_owner(pool, db_org).await {
+        Ok(org) => (status, Json(ApiResponse::success(org))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<DiscordOrg>::error(format!(
+                "Database error: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Create a new org owned by the caller
+pub async fn create_org(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Json(request): Json<CreateOrgRequest>,
+) -> Response {
+    if request.name.trim().is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<DiscordOrg>::error(
+                "Organization name is required".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    let tags = match normalize_tags(request.tags.unwrap_or_default()) {
+        Ok(tags) => tags,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::<DiscordOrg>::error(e)),
+            )
+                .into_response();
+        }
+    };
+
+    let create_org = CreateDiscordOrg {
+        owner_id: auth.user_id,
+        name: request.name.trim().to_string(),
+        avatar_url: request.avatar_url,
+        description: request.description,
+        guild_id: None,
+        requires_approval: request.requires_approval.unwrap_or(false),
+        listed: request.listed.unwrap_or(false),
+        tags,
+    };
+
+    match queries::create_discord_org(&state.db_pool, create_org).await {
+        Ok(db_org) => org_response(&state.db_pool, db_org, StatusCode::CREATED).await,
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<DiscordOrg>::error(format!(
+                "Failed to create organization: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// List orgs: admins see every org (paginated), everyone else the orgs they own
+pub async fn list_orgs(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Query(params): Query<ListOrgsQuery>,
+) -> Response {
+    let db_orgs = if auth.is_admin {
+        let limit = params.limit.unwrap_or(50).min(100); // Max 100 items per page
+        let offset = params.offset.unwrap_or(0).max(0); // Ensure non-negative
+        queries::list_discord_orgs(&state.db_pool, limit, offset).await
+    } else {
+        queries::get_discord_orgs_by_owner(&state.db_pool, auth.user_id).await
+    };
+
+    let orgs = match db_orgs {
+        Ok(db_orgs) => orgs_with_owners(&state.db_pool, db_orgs).await,
+        Err(e) => Err(e),
+    };
+
+    match orgs {
+        Ok(orgs) => (StatusCode::OK, Json(ApiResponse::success(orgs))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<DiscordOrg>>::error(format!(
+                "Failed to list organizations: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Search the public directory of listed orgs (no authentication needed). Supports
+/// `?q=` full-text search, `?tag=` filtering and `?sort=relevance|members|newest`.
+pub async fn discover_orgs(
+    State(state): State<crate::AppState>,
+    Query(params): Query<DiscoverOrgsQuery>,
+) -> Response {
+    let limit = params.limit.unwrap_or(20).clamp(1, 100); // Max 100 items per page
+    let offset = params.offset.unwrap_or(0).max(0); // Ensure non-negative
+    let query = params.q.as_deref().map(str::trim).filter(|q| !q.is_empty());
+    let tag = params
+        .tag
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty());
+
+    match queries::search_listed_discord_orgs(
+        &state.db_pool,
+        query,
+        tag.as_deref(),
+        params.sort.as_str(),
+        limit,
+        offset,
+    )
+    .await
+    {
+        Ok(db_listings) => {
+            let listings: Vec<OrgListing> = db_listings.into_iter().map(OrgListing::from).collect();
+            (StatusCode::OK, Json(ApiResponse::success(listings))).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<OrgListing>>::error(format!(
+                "Failed to search organizations: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Get an org by ID (owner or admins)
+pub async fn get_org(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    if let Err(e) =
+        permissions::require_org_permission(&state.db_pool, &auth, org_id, Permissions::MANAGE_ORG)
+            .await
+    {
+        return e.into_response();
+    }
+
+    match queries::get_discord_org_by_id(&state.db_pool, org_id).await {
+        Ok(Some(db_org)) => org_response(&state.db_pool, db_org, StatusCode::OK).await,
+        Ok(None) => permissions::PermissionError::OrgNotFound.into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<DiscordOrg>::error(format!(
+                "Database error: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Update an org (owner or admins)
+pub async fn update_org(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Json(mut update_org): Json<UpdateDiscordOrg>,
+) -> Response {
+    if let Err(e) =
+        permissions::require_org_permission(&state.db_pool, &auth, org_id, Permissions::MANAGE_ORG)
+            .await
+    {
+        return e.into_response();
+    }
+
+    if let Some(tags) = update_org.tags.take() {
+        match normalize_tags(tags) {
+            Ok(tags) => update_org.tags = Some(tags),
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::<DiscordOrg>::error(e)),
+                )
+                    .into_response();
+            }
+        }
+    }
+
+    match queries::update_discord_org(&state.db_pool, org_id, update_org).await {
+        Ok(Some(db_org)) => org_response(&state.db_pool, db_org, StatusCode::OK).await,
+        Ok(None) => permissions::PermissionError::OrgNotFound.into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<DiscordOrg>::error(format!(
+                "Failed to update organization: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Delete an org (owner or global admins); it can be restored by a global admin until
+/// it is purged
+pub async fn delete_org(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    if let Err(e) =
+        permissions::require_org_permission(&state.db_pool, &auth, org_id, Permissions::DELETE_ORG)
+            .await
+    {
+        return e.into_response();
+    }
+
+    match queries::delete_discord_org(&state.db_pool, org_id).await {
+        Ok(true) => (StatusCode::NO_CONTENT, Json(ApiResponse::<()>::success(()))).into_response(),
+        Ok(false) => permissions::PermissionError::OrgNotFound.into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to delete organization: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Restore a deleted org (global admins, within the retention window)
+pub async fn restore_org(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    if !auth.is_admin {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<DiscordOrg>::error(
+                "Only admins can restore organizations".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    match queries::restore_discord_org(&state.db_pool, org_id, tasks::purge::retention_cutoff())
+        .await
+    {
+        Ok(Some(db_org)) => org_response(&state.db_pool, db_org, StatusCode::OK).await,
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<DiscordOrg>::error(
+                "No restorable deleted organization with this ID".to_string(),
+            )),
+        )
+            .into_response(),
+        Err(e) if e.to_string().contains("duplicate key") => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<DiscordOrg>::error(
+                "This organization's guild has been imported again since it was deleted"
+                    .to_string(),
+            )),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<DiscordOrg>::error(format!(
+                "Failed to restore organization: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tags() {
+        assert_eq!(
+            normalize_tags(vec![
+                " Chess ".to_string(),
+                "chess".to_string(),
+                "FPS".to_string()
+            ]),
+            Ok(vec!["chess".to_string(), "fps".to_string()])
+        );
+        assert!(normalize_tags(vec!["  ".to_string()]).is_err());
+        assert!(normalize_tags(vec!["x".repeat(MAX_ORG_TAG_LENGTH + 1)]).is_err());
+        assert!(normalize_tags((0..=MAX_ORG_TAGS).map(|i| i.to_string()).collect()).is_err());
+    }
+}
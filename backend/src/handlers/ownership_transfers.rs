@@ -0,0 +1,238 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::orgs::org_response;
+use super::users::ApiResponse;
+use crate::auth::AuthUser;
+use crate::auth::permissions;
+use crate::config::Config;
+use crate::database::queries::{self, OwnershipTransferAcceptance};
+use shared::database::{CreateOwnershipTransfer, DbOwnershipTransfer};
+use shared::models::{DiscordOrg, MemberStatus, OwnershipTransfer, Permissions};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateOwnershipTransferRequest {
+    pub member_id: Uuid,
+}
+
+fn transfers_response(result: Result<Vec<DbOwnershipTransfer>, sqlx::Error>) -> Response {
+    let transfers = match result {
+        Ok(db_transfers) => db_transfers
+            .into_iter()
+            .map(OwnershipTransfer::try_from)
+            .collect::<Result<Vec<_>, _>>(),
+        Err(e) => Err(format!("Database error: {}", e)),
+    };
+
+    match transfers {
+        Ok(transfers) => (StatusCode::OK, Json(ApiResponse::success(transfers))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<OwnershipTransfer>>::error(e)),
+        )
+            .into_response(),
+    }
+}
+
+/// Nominate a member as the org's next owner (requires `TRANSFER_OWNERSHIP`); replaces
+/// any pending nomination
+pub async fn create_ownership_transfer(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Json(request): Json<CreateOwnershipTransferRequest>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::TRANSFER_OWNERSHIP,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    let org = match queries::get_discord_org_by_id(&state.db_pool, org_id).await {
+        Ok(Some(org)) => org,
+        Ok(None) => return permissions::PermissionError::OrgNotFound.into_response(),
+        Err(e) => return permissions::PermissionError::Database(e.to_string()).into_response(),
+    };
+
+    let member = match queries::get_member_by_id(&state.db_pool, request.member_id).await {
+        Ok(Some(member)) if member.discord_org_id == org_id => member,
+        Ok(_) => return permissions::PermissionError::MemberNotFound.into_response(),
+        Err(e) => return permissions::PermissionError::Database(e.to_string()).into_response(),
+    };
+
+    let error = if member.user_id == org.owner_id {
+        Some("This member already owns the organization")
+    } else if member.status == String::from(MemberStatus::Banned) {
+        Some("Banned members can't become the owner")
+    } else {
+        None
+    };
+    if let Some(error) = error {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<OwnershipTransfer>::error(error.to_string())),
+        )
+            .into_response();
+    }
+
+    let create_transfer = CreateOwnershipTransfer {
+        discord_org_id: org_id,
+        from_user_id: org.owner_id,
+        to_user_id: member.user_id,
+        initiated_by: Some(auth.user_id),
+        expires_at: chrono::Utc::now()
+            + chrono::Duration::seconds(Config::ownership_transfer_ttl_seconds()),
+    };
+
+    let transfer = match queries::create_ownership_transfer(&state.db_pool, create_transfer).await {
+        Ok(db_transfer) => OwnershipTransfer::try_from(db_transfer),
+        Err(e) => Err(format!("Failed to create ownership transfer: {}", e)),
+    };
+
+    match transfer {
+        Ok(transfer) => (StatusCode::CREATED, Json(ApiResponse::success(transfer))).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<OwnershipTransfer>::error(e)),
+        )
+            .into_response(),
+    }
+}
+
+/// Cancel the org's pending ownership transfer (requires `TRANSFER_OWNERSHIP`)
+pub async fn cancel_ownership_transfer(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    if let Err(e) = permissions::require_org_permission(
+        &state.db_pool,
+        &auth,
+        org_id,
+        Permissions::TRANSFER_OWNERSHIP,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    match queries::cancel_ownership_transfer(&state.db_pool, org_id).await {
+        Ok(true) => (StatusCode::NO_CONTENT, Json(ApiResponse::<()>::success(()))).into_response(),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::error(
+                "This organization has no pending ownership transfer".to_string(),
+            )),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to cancel ownership transfer: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// List every ownership transfer of an org, newest first, for auditing (requires
+/// `MANAGE_ORG`)
+pub async fn list_ownership_transfers(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    if let Err(e) =
+        permissions::require_org_permission(&state.db_pool, &auth, org_id, Permissions::MANAGE_ORG)
+            .await
+    {
+        return e.into_response();
+    }
+
+    transfers_response(queries::get_ownership_transfers_by_org(&state.db_pool, org_id).await)
+}
+
+/// List the pending ownership transfers nominating the caller
+pub async fn list_my_ownership_transfers(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+) -> Response {
+    transfers_response(
+        queries::get_pending_ownership_transfers_for_user(&state.db_pool, auth.user_id).await,
+    )
+}
+
+/// Accept an ownership transfer nominating the caller; the previous owner becomes an admin
+pub async fn accept_ownership_transfer(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(transfer_id): Path<Uuid>,
+) -> Response {
+    let (status, error_msg) =
+        match queries::accept_ownership_transfer(&state.db_pool, transfer_id, auth.user_id).await {
+            Ok(OwnershipTransferAcceptance::Accepted(db_org)) => {
+                return org_response(&state.db_pool, db_org, StatusCode::OK).await;
+            }
+            Ok(OwnershipTransferAcceptance::NotFound) => (
+                StatusCode::NOT_FOUND,
+                "Pending ownership transfer not found".to_string(),
+            ),
+            Ok(OwnershipTransferAcceptance::Expired) => (
+                StatusCode::GONE,
+                "This ownership transfer has expired".to_string(),
+            ),
+            Ok(OwnershipTransferAcceptance::OwnerChanged) => (
+                StatusCode::CONFLICT,
+                "The organization changed owners since this transfer was created".to_string(),
+            ),
+            Ok(OwnershipTransferAcceptance::NotMember) => (
+                StatusCode::CONFLICT,
+                "You have to be a member in good standing to take over the organization"
+                    .to_string(),
+            ),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to accept ownership transfer: {}", e),
+            ),
+        };
+
+    (status, Json(ApiResponse::<DiscordOrg>::error(error_msg))).into_response()
+}
+
+/// Decline an ownership transfer nominating the caller
+pub async fn decline_ownership_transfer(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(transfer_id): Path<Uuid>,
+) -> Response {
+    match queries::decline_ownership_transfer(&state.db_pool, transfer_id, auth.user_id).await {
+        Ok(true) => (StatusCode::NO_CONTENT, Json(ApiResponse::<()>::success(()))).into_response(),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::error(
+                "Pending ownership transfer not found".to_string(),
+            )),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to decline ownership transfer: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
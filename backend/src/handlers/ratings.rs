@@ -0,0 +1,222 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::seasons::load_season;
+use super::users::ApiResponse;
+use crate::auth::permissions;
+use crate::auth::{AuthUser, Caller};
+use crate::database::queries;
+use shared::models::{ApiKeyScope, LeaderboardEntry, Permissions, PlayerRating, RatingChange};
+
+#[derive(Debug, Deserialize)]
+pub struct LeaderboardQuery {
+    pub game_type: Option<String>,
+    pub season: Option<Uuid>, // An archived season's final standings instead of live ratings
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RatingQuery {
+    pub game_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecomputeRatingsRequest {
+    pub game_type: String,
+}
+
+/// A player's current rating with every change that led to it
+#[derive(Debug, Serialize)]
+pub struct RatingWithHistory {
+    pub rating: PlayerRating,
+    pub history: Vec<RatingChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecomputedRatings {
+    pub matches_replayed: i64,
+}
+
+/// Ratings are kept per game type, so every rating endpoint needs one
+fn require_game_type(game_type: Option<String>) -> Result<String, String> {
+    game_type
+        .map(|game_type| game_type.trim().to_lowercase())
+        .filter(|game_type| !game_type.is_empty())
+        .ok_or_else(|| "A game type is required".to_string())
+}
+
+/// An org's leaderboard for one game type, highest rating first; `?season=...` shows
+/// an archived season's final standings (users need `VIEW_MEMBERS`, API keys the
+/// `leaderboard:read` scope)
+pub async fn get_leaderboard(
+    State(state): State<crate::AppState>,
+    caller: Caller,
+    Path(org_id): Path<Uuid>,
+    Query(params): Query<LeaderboardQuery>,
+) -> Response {
+    if let Err(e) = permissions::require_caller_access(
+        &state.db_pool,
+        &caller,
+        org_id,
+        Permissions::VIEW_MEMBERS,
+        ApiKeyScope::LeaderboardRead,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    let game_type = match require_game_type(params.game_type) {
+        Ok(game_type) => game_type,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::<Vec<LeaderboardEntry>>::error(e)),
+            )
+                .into_response();
+        }
+    };
+    let limit = params.limit.unwrap_or(50).clamp(1, 100); // Max 100 items per page
+    let offset = params.offset.unwrap_or(0).max(0); // Ensure non-negative
+
+    // A season that hasn't rolled over yet is still being played, so it shows the
+    // live ratings
+    let archived_season = match params.season {
+        Some(season_id) => match load_season(&state.db_pool, Some(org_id), season_id).await {
+            Ok(season) => season.archived_at.map(|_| season.id),
+            Err(response) => return response,
+        },
+        None => None,
+    };
+
+    let entries = match archived_season {
+        Some(season_id) => {
+            queries::get_season_standings(&state.db_pool, season_id, &game_type, limit, offset)
+                .await
+        }
+        None => queries::get_leaderboard(&state.db_pool, org_id, &game_type, limit, offset).await,
+    };
+
+    match entries {
+        Ok(db_entries) => {
+            let entries: Vec<LeaderboardEntry> =
+                db_entries.into_iter().map(LeaderboardEntry::from).collect();
+            (StatusCode::OK, Json(ApiResponse::success(entries))).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<LeaderboardEntry>>::error(format!(
+                "Failed to get leaderboard: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// A player's rating for one game type with its history, for charting progress
+/// (users need `VIEW_MEMBERS`, API keys the `leaderboard:read` scope)
+pub async fn get_player_rating(
+    State(state): State<crate::AppState>,
+    caller: Caller,
+    Path((org_id, user_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<RatingQuery>,
+) -> Response {
+    if let Err(e) = permissions::require_caller_access(
+        &state.db_pool,
+        &caller,
+        org_id,
+        Permissions::VIEW_MEMBERS,
+        ApiKeyScope::LeaderboardRead,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    let game_type = match require_game_type(params.game_type) {
+        Ok(game_type) => game_type,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::<RatingWithHistory>::error(e)),
+            )
+                .into_response();
+        }
+    };
+
+    let rating = match queries::get_rating(&state.db_pool, org_id, &game_type, user_id).await {
+        Ok(Some(db_rating)) => PlayerRating::from(db_rating),
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::<RatingWithHistory>::error(
+                    "This player has no rated matches for that game type".to_string(),
+                )),
+            )
+                .into_response();
+        }
+        Err(e) => return permissions::PermissionError::Database(e.to_string()).into_response(),
+    };
+
+    match queries::get_rating_history(&state.db_pool, org_id, &game_type, user_id).await {
+        Ok(db_history) => {
+            let rating = RatingWithHistory {
+                rating,
+                history: db_history.into_iter().map(RatingChange::from).collect(),
+            };
+            (StatusCode::OK, Json(ApiResponse::success(rating))).into_response()
+        }
+        Err(e) => permissions::PermissionError::Database(e.to_string()).into_response(),
+    }
+}
+
+/// Rebuild an org's ratings for one game type from its finalized matches (requires
+/// `MANAGE_ORG`)
+pub async fn recompute_ratings(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Json(request): Json<RecomputeRatingsRequest>,
+) -> Response {
+    if let Err(e) =
+        permissions::require_org_permission(&state.db_pool, &auth, org_id, Permissions::MANAGE_ORG)
+            .await
+    {
+        return e.into_response();
+    }
+
+    let game_type = match require_game_type(Some(request.game_type)) {
+        Ok(game_type) => game_type,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::<RecomputedRatings>::error(e)),
+            )
+                .into_response();
+        }
+    };
+
+    match queries::recompute_ratings(&state.db_pool, org_id, &game_type).await {
+        Ok(matches_replayed) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(RecomputedRatings { matches_replayed })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<RecomputedRatings>::error(format!(
+                "Failed to recompute ratings: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
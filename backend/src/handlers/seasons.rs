@@ -0,0 +1,268 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::users::ApiResponse;
+use crate::auth::permissions;
+use crate::auth::{AuthUser, Caller};
+use crate::database::{DatabasePool, queries};
+use shared::database::{CreateSeason, DbSeason};
+use shared::models::{ApiKeyScope, Permissions, Season};
+
+const MAX_SEASON_NAME_LENGTH: usize = 100;
+const DEFAULT_SOFT_RESET: f64 = 0.5;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSeasonRequest {
+    pub name: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub soft_reset: Option<f64>, // Defaults to halfway back to the default rating
+}
+
+/// Check a new season's name, dates and reset strength
+fn validate_season(
+    name: &str,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    soft_reset: f64,
+) -> Result<(), String> {
+    if name.is_empty() || name.chars().count() > MAX_SEASON_NAME_LENGTH {
+        return Err(format!(
+            "A season name must be between 1 and {} characters",
+            MAX_SEASON_NAME_LENGTH
+        ));
+    }
+    if ends_at <= starts_at {
+        return Err("A season must end after it starts".to_string());
+    }
+    if ends_at <= Utc::now() {
+        return Err("A season must end in the future".to_string());
+    }
+    if !(0.0..=1.0).contains(&soft_reset) {
+        return Err("The soft reset must be between 0 and 1".to_string());
+    }
+
+    Ok(())
+}
+
+/// Load a season, answering 404 unless it belongs to `org_id` (when one is given)
+pub(crate) async fn load_season(
+    pool: &DatabasePool,
+    org_id: Option<Uuid>,
+    season_id: Uuid,
+) -> Result<DbSeason, Response> {
+    queries::get_season_by_id(pool, season_id)
+        .await
+        .map_err(|e| permissions::PermissionError::Database(e.to_string()).into_response())?
+        .filter(|season| org_id.is_none_or(|org_id| season.discord_org_id == org_id))
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::<Season>::error("Season not found".to_string())),
+            )
+                .into_response()
+        })
+}
+
+/// Schedule a season for an org (requires `MANAGE_ORG`); seasons can't overlap
+pub async fn create_season(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(org_id): Path<Uuid>,
+    Json(request): Json<CreateSeasonRequest>,
+) -> Response {
+    if let Err(e) =
+        permissions::require_org_permission(&state.db_pool, &auth, org_id, Permissions::MANAGE_ORG)
+            .await
+    {
+        return e.into_response();
+    }
+
+    let name = request.name.trim().to_string();
+    let soft_reset = request.soft_reset.unwrap_or(DEFAULT_SOFT_RESET);
+    if let Err(error) = validate_season(&name, request.starts_at, request.ends_at, soft_reset) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<Season>::error(error)),
+        )
+            .into_response();
+    }
+
+    let create_season = CreateSeason {
+        discord_org_id: org_id,
+        name,
+        starts_at: request.starts_at,
+        ends_at: request.ends_at,
+        soft_reset,
+        created_by: Some(auth.user_id),
+    };
+
+    match queries::create_season(&state.db_pool, create_season).await {
+        Ok(Some(db_season)) => (
+            StatusCode::CREATED,
+            Json(ApiResponse::success(Season::from(db_season))),
+        )
+            .into_response(),
+        Ok(None) => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<Season>::error(
+                "This season overlaps another season of the organization".to_string(),
+            )),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Season>::error(format!(
+                "Failed to create season: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// List an org's seasons, latest first (users need `VIEW_MEMBERS`, API keys the
+/// `leaderboard:read` scope)
+pub async fn list_seasons(
+    State(state): State<crate::AppState>,
+    caller: Caller,
+    Path(org_id): Path<Uuid>,
+) -> Response {
+    if let Err(e) = permissions::require_caller_access(
+        &state.db_pool,
+        &caller,
+        org_id,
+        Permissions::VIEW_MEMBERS,
+        ApiKeyScope::LeaderboardRead,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    match queries::get_seasons_by_org(&state.db_pool, org_id).await {
+        Ok(db_seasons) => {
+            let seasons: Vec<Season> = db_seasons.into_iter().map(Season::from).collect();
+            (StatusCode::OK, Json(ApiResponse::success(seasons))).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<Season>>::error(format!(
+                "Failed to get seasons: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+/// Get one of an org's seasons (users need `VIEW_MEMBERS`, API keys the
+/// `leaderboard:read` scope)
+pub async fn get_season(
+    State(state): State<crate::AppState>,
+    caller: Caller,
+    Path((org_id, season_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(e) = permissions::require_caller_access(
+        &state.db_pool,
+        &caller,
+        org_id,
+        Permissions::VIEW_MEMBERS,
+        ApiKeyScope::LeaderboardRead,
+    )
+    .await
+    {
+        return e.into_response();
+    }
+
+    match load_season(&state.db_pool, Some(org_id), season_id).await {
+        Ok(db_season) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(Season::from(db_season))),
+        )
+            .into_response(),
+        Err(response) => response,
+    }
+}
+
+/// Delete a season that hasn't rolled over yet (requires `MANAGE_ORG`)
+pub async fn delete_season(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path((org_id, season_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(e) =
+        permissions::require_org_permission(&state.db_pool, &auth, org_id, Permissions::MANAGE_ORG)
+            .await
+    {
+        return e.into_response();
+    }
+
+    if let Err(response) = load_season(&state.db_pool, Some(org_id), season_id).await {
+        return response;
+    }
+
+    match queries::delete_season(&state.db_pool, season_id).await {
+        Ok(true) => (StatusCode::NO_CONTENT, Json(ApiResponse::<()>::success(()))).into_response(),
+        Ok(false) => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::<()>::error(
+                "This season has already been archived".to_string(),
+            )),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to delete season: {}",
+                e
+            ))),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_validate_season() {
+        let now = Utc::now();
+        let next_month = now + Duration::days(30);
+
+        assert!(validate_season("Spring", now, next_month, 0.5).is_ok());
+        assert!(validate_season("Spring", now, next_month, 0.0).is_ok());
+        assert!(validate_season("Spring", now, next_month, 1.0).is_ok());
+        assert!(validate_season("", now, next_month, 0.5).is_err());
+        assert!(
+            validate_season(
+                &"x".repeat(MAX_SEASON_NAME_LENGTH + 1),
+                now,
+                next_month,
+                0.5
+            )
+            .is_err()
+        );
+        assert!(validate_season("Spring", next_month, now, 0.5).is_err());
+        assert!(
+            validate_season(
+                "Spring",
+                now - Duration::days(60),
+                now - Duration::days(30),
+                0.5
+            )
+            .is_err()
+        );
+        assert!(validate_season("Spring", now, next_month, 1.5).is_err());
+        assert!(validate_season("Spring", now, next_month, f64::NAN).is_err());
+    }
+}
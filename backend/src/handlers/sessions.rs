@@ -0,0 +1,119 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::users::ApiResponse;
+use crate::auth::AuthUser;
+use crate::database::queries;
+use shared::database::DbSession;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionResponse {
+    pub id: Uuid,
+    pub device_label: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub current: bool, // Whether this is the session making the request
+}
+
+impl SessionResponse {
+    fn from_session(session: DbSession, current_session_id: Uuid) -> Self {
+        Self {
+            id: session.id,
+            device_label: session.device_label,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            created_at: session.created_at,
+            last_seen_at: session.last_seen_at,
+            expires_at: session.expires_at,
+            current: session.id == current_session_id,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RevokeAllQuery {
+    pub except_current: Option<bool>,
+}
+
+/// List the caller's active sessions across all devices
+pub async fn list_sessions(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+) -> impl IntoResponse {
+    match queries::get_active_sessions_by_user(&state.db_pool, auth.user_id).await {
+        Ok(sessions) => {
+            let sessions: Vec<SessionResponse> = sessions
+                .into_iter()
+                .map(|session| SessionResponse::from_session(session, auth.session_id))
+                .collect();
+            (StatusCode::OK, Json(ApiResponse::success(sessions)))
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<Vec<SessionResponse>>::error(format!(
+                "Database error: {}",
+                e
+            ))),
+        ),
+    }
+}
+
+/// Revoke one of the caller's sessions
+pub async fn revoke_session(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(session_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match queries::revoke_user_session(&state.db_pool, auth.user_id, session_id).await {
+        Ok(true) => (StatusCode::OK, Json(ApiResponse::success(()))),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::error("Session not found".to_string())),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(format!(
+                "Failed to revoke session: {}",
+                e
+            ))),
+        ),
+    }
+}
+
+/// Revoke all of the caller's sessions, or all but the current one with `?except_current=true`
+pub async fn revoke_all_sessions(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Query(query): Query<RevokeAllQuery>,
+) -> impl IntoResponse {
+    let except_session_id = query
+        .except_current
+        .unwrap_or(false)
+        .then_some(auth.session_id);
+
+    match queries::revoke_all_user_sessions(&state.db_pool, auth.user_id, except_session_id).await {
+        Ok(revoked_count) => {
+            let result = serde_json::json!({
+                "revoked_count": revoked_count,
+                "message": format!("Revoked {} sessions", revoked_count)
+            });
+            (StatusCode::OK, Json(ApiResponse::success(result)))
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<serde_json::Value>::error(format!(
+                "Failed to revoke sessions: {}",
+                e
+            ))),
+        ),
+    }
+}
@@ -8,7 +8,9 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::auth::AuthUser;
 use crate::database::{DatabasePool, queries};
+use crate::tasks;
 use shared::database::{CreateUser, UpdateUser};
 use shared::models::User;
 
@@ -43,11 +45,21 @@ impl<T> ApiResponse<T> {
     }
 }
 
-/// Create a new user
+/// Create a new user (admin only; regular users are created by the OAuth flow)
 pub async fn create_user(
     State(state): State<crate::AppState>,
+    auth: AuthUser,
     Json(create_user): Json<CreateUser>,
 ) -> impl IntoResponse {
+    if !auth.is_admin {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<User>::error(
+                "Only admins can create users".to_string(),
+            )),
+        );
+    }
+
     match queries::create_user(&state.db_pool, create_user).await {
         Ok(db_user) => {
             let user: User = db_user.into();
@@ -70,6 +82,7 @@ pub async fn create_user(
 /// Get user by ID
 pub async fn get_user(
     State(state): State<crate::AppState>,
+    _auth: AuthUser,
     Path(user_id): Path<Uuid>,
 ) -> impl IntoResponse {
     match queries::get_user_by_id(&state.db_pool, user_id).await {
@@ -91,6 +104,7 @@ pub async fn get_user(
 /// Get user by Discord ID
 pub async fn get_user_by_discord_id(
     State(state): State<crate::AppState>,
+    _auth: AuthUser,
     Path(discord_id): Path<String>,
 ) -> impl IntoResponse {
     match queries::get_user_by_discord_id(&state.db_pool, &discord_id).await {
@@ -112,9 +126,19 @@ pub async fn get_user_by_discord_id(
 /// Update user
 pub async fn update_user(
     State(state): State<crate::AppState>,
+    auth: AuthUser,
     Path(user_id): Path<Uuid>,
     Json(update_user): Json<UpdateUser>,
 ) -> impl IntoResponse {
+    if !auth.can_act_for(user_id) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<User>::error(
+                "You can only modify your own account".to_string(),
+            )),
+        );
+    }
+
     match queries::update_user(&state.db_pool, user_id, update_user).await {
         Ok(Some(db_user)) => {
             let user: User = db_user.into();
@@ -134,11 +158,21 @@ pub async fn update_user(
     }
 }
 
-/// Delete user
+/// Delete user; the account can be restored by an admin until it is purged
 pub async fn delete_user(
     State(state): State<crate::AppState>,
+    auth: AuthUser,
     Path(user_id): Path<Uuid>,
 ) -> impl IntoResponse {
+    if !auth.can_act_for(user_id) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<()>::error(
+                "You can only delete your own account".to_string(),
+            )),
+        );
+    }
+
     match queries::delete_user(&state.db_pool, user_id).await {
         Ok(true) => (StatusCode::NO_CONTENT, Json(ApiResponse::<()>::success(()))),
         Ok(false) => (
@@ -155,9 +189,47 @@ pub async fn delete_user(
     }
 }
 
+/// Restore a deleted user and the orgs deleted with them (admin only, within the
+/// retention window)
+pub async fn restore_user(
+    State(state): State<crate::AppState>,
+    auth: AuthUser,
+    Path(user_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !auth.is_admin {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<User>::error(
+                "Only admins can restore users".to_string(),
+            )),
+        );
+    }
+
+    match queries::restore_user(&state.db_pool, user_id, tasks::purge::retention_cutoff()).await {
+        Ok(Some(db_user)) => {
+            let user: User = db_user.into();
+            (StatusCode::OK, Json(ApiResponse::success(user)))
+        }
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<User>::error(
+                "No restorable deleted user with this ID".to_string(),
+            )),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<User>::error(format!(
+                "Failed to restore user: {}",
+                e
+            ))),
+        ),
+    }
+}
+
 /// List users with pagination
 pub async fn list_users(
     State(state): State<crate::AppState>,
+    _auth: AuthUser,
     Query(params): Query<ListUsersQuery>,
 ) -> impl IntoResponse {
     let limit = params.limit.unwrap_or(50).min(100); // Max 100 items per page
@@ -186,10 +258,13 @@ pub struct UserStats {
 }
 
 /// Get user statistics
-pub async fn get_user_stats(State(state): State<crate::AppState>) -> impl IntoResponse {
+pub async fn get_user_stats(
+    State(state): State<crate::AppState>,
+    _auth: AuthUser,
+) -> impl IntoResponse {
     // Note: This would require additional query functions
     // For now, we'll return a simple count
-    match sqlx::query_scalar!("SELECT COUNT(*) FROM users")
+    match sqlx::query_scalar!("SELECT COUNT(*) FROM users WHERE deleted_at IS NULL")
         .fetch_one(&*state.db_pool)
         .await
     {
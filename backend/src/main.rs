@@ -1,20 +1,29 @@
 use axum::{
     Json, Router,
-    extract::{Query, State},
+    extract::{ConnectInfo, Query, State},
+    http::{HeaderMap, StatusCode, header::SET_COOKIE},
     response::{IntoResponse, Redirect},
-    routing::{get, post},
+    routing::{delete, get, post, put},
 };
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::env;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tower::ServiceBuilder;
 use tower_http::cors::{Any, CorsLayer};
 
+mod auth;
 mod config;
+mod crypto;
 mod database;
+mod discord_api;
 mod handlers;
+mod tasks;
 
+use auth::AuthUser;
+use auth::oauth_state::IssueStateError;
+use auth::session::SessionDevice;
 use config::Config;
 use database::{DatabasePool, create_pool, health_check as db_health_check};
 
@@ -32,8 +41,15 @@ struct DiscordCallbackQuery {
 }
 
 #[derive(Deserialize)]
-struct DiscordExchangeQuery {
+struct DiscordStateRequest {
+    code_challenge: String,
+}
+
+#[derive(Deserialize)]
+struct DiscordExchangeRequest {
     code: String,
+    state: Option<String>,
+    code_verifier: Option<String>,
     frontend_redirect: Option<String>,
 }
 
@@ -45,10 +61,42 @@ async fn main() {
     let db_pool = create_pool().await.expect("Failed to create database pool");
     println!("Database connected! Run 'sqlx migrate run' to apply migrations if needed.");
 
+    // `backend rotate-token-keys` re-encrypts stored Discord tokens with the active key
+    if env::args().nth(1).as_deref() == Some("rotate-token-keys") {
+        match database::queries::rotate_discord_token_keys(&db_pool).await {
+            Ok(rotation) => {
+                println!("🔑 Re-encrypted {} Discord tokens", rotation.rotated);
+                if !rotation.failed.is_empty() {
+                    for (user_id, e) in &rotation.failed {
+                        eprintln!("   - user_id {}: {}", user_id, e);
+                    }
+                    // Retiring the old key now would lock these users out
+                    eprintln!(
+                        "❌ {} Discord tokens could not be re-encrypted",
+                        rotation.failed.len()
+                    );
+                    std::process::exit(1);
+                }
+            }
+            Err(e) => {
+                eprintln!("❌ Token key rotation failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     let state = AppState {
         db_pool: Arc::new(db_pool),
     };
 
+    // Start background tasks
+    tasks::token_refresh::spawn(state.db_pool.clone());
+    tasks::ban_expiry::spawn(state.db_pool.clone());
+    tasks::purge::spawn(state.db_pool.clone());
+    tasks::match_finalization::spawn(state.db_pool.clone());
+    tasks::season_rollover::spawn(state.db_pool.clone());
+
     // API routes
     let api_routes = Router::new()
         .route(
@@ -61,11 +109,17 @@ async fn main() {
                 .put(handlers::update_user)
                 .delete(handlers::delete_user),
         )
+        .route("/users/:id/restore", post(handlers::users::restore_user))
         .route(
             "/users/discord/:discord_id",
             get(handlers::get_user_by_discord_id),
         )
         .route("/users/stats", get(handlers::get_user_stats))
+        .route(
+            "/users/:id/memberships",
+            get(handlers::members::list_user_memberships),
+        )
+        .route("/users/:id/stats", get(handlers::matches::get_user_stats))
         // Discord token routes
         .route(
             "/discord-tokens",
@@ -84,11 +138,183 @@ async fn main() {
         .route(
             "/discord-tokens/cleanup",
             post(handlers::discord_tokens::cleanup_expired_tokens),
-        );
+        )
+        // Org routes
+        .route("/orgs/discover", get(handlers::orgs::discover_orgs))
+        .route(
+            "/orgs",
+            get(handlers::orgs::list_orgs).post(handlers::orgs::create_org),
+        )
+        .route(
+            "/orgs/:id",
+            get(handlers::orgs::get_org)
+                .put(handlers::orgs::update_org)
+                .delete(handlers::orgs::delete_org),
+        )
+        .route("/orgs/:id/restore", post(handlers::orgs::restore_org))
+        // Ownership transfer routes
+        .route(
+            "/orgs/:org_id/transfer",
+            post(handlers::ownership_transfers::create_ownership_transfer)
+                .delete(handlers::ownership_transfers::cancel_ownership_transfer),
+        )
+        .route(
+            "/orgs/:org_id/transfers",
+            get(handlers::ownership_transfers::list_ownership_transfers),
+        )
+        .route(
+            "/ownership-transfers",
+            get(handlers::ownership_transfers::list_my_ownership_transfers),
+        )
+        .route(
+            "/ownership-transfers/:transfer_id/accept",
+            post(handlers::ownership_transfers::accept_ownership_transfer),
+        )
+        .route(
+            "/ownership-transfers/:transfer_id/decline",
+            post(handlers::ownership_transfers::decline_ownership_transfer),
+        )
+        // Org membership routes
+        .route("/orgs/import", post(handlers::guilds::import_guild))
+        .route("/discord/guilds", get(handlers::guilds::list_guilds))
+        .route(
+            "/orgs/:org_id/permissions",
+            get(handlers::members::get_org_permissions),
+        )
+        .route(
+            "/orgs/:org_id/members",
+            get(handlers::members::list_org_members).post(handlers::members::join_org),
+        )
+        .route(
+            "/orgs/:org_id/members/me",
+            delete(handlers::members::leave_org),
+        )
+        .route(
+            "/orgs/:org_id/members/:member_id/role",
+            put(handlers::members::update_member_role),
+        )
+        .route(
+            "/orgs/:org_id/members/:member_id/status",
+            put(handlers::members::update_member_status),
+        )
+        .route(
+            "/orgs/:org_id/join-requests",
+            get(handlers::join_requests::list_join_requests),
+        )
+        .route(
+            "/orgs/:org_id/join-requests/:request_id/approve",
+            post(handlers::join_requests::approve_join_request),
+        )
+        .route(
+            "/orgs/:org_id/join-requests/:request_id/reject",
+            post(handlers::join_requests::reject_join_request),
+        )
+        .route(
+            "/join-requests",
+            get(handlers::join_requests::list_my_join_requests),
+        )
+        .route(
+            "/join-requests/:request_id",
+            delete(handlers::join_requests::withdraw_join_request),
+        )
+        .route(
+            "/orgs/:org_id/members/:member_id/history",
+            get(handlers::members::get_member_status_history),
+        )
+        // Match routes
+        .route(
+            "/orgs/:org_id/matches",
+            get(handlers::matches::list_matches).post(handlers::matches::record_match),
+        )
+        .route(
+            "/orgs/:org_id/matches/disputes",
+            get(handlers::matches::list_disputed_matches),
+        )
+        .route(
+            "/orgs/:org_id/matches/:match_id",
+            get(handlers::matches::get_match)
+                .put(handlers::matches::update_match)
+                .delete(handlers::matches::delete_match),
+        )
+        .route(
+            "/orgs/:org_id/matches/:match_id/confirm",
+            post(handlers::matches::confirm_match),
+        )
+        .route(
+            "/orgs/:org_id/matches/:match_id/dispute",
+            post(handlers::matches::dispute_match),
+        )
+        .route(
+            "/orgs/:org_id/matches/:match_id/resolve",
+            post(handlers::matches::resolve_match),
+        )
+        // Rating routes
+        .route(
+            "/orgs/:org_id/leaderboard",
+            get(handlers::ratings::get_leaderboard),
+        )
+        .route(
+            "/orgs/:org_id/ratings/recompute",
+            post(handlers::ratings::recompute_ratings),
+        )
+        .route(
+            "/orgs/:org_id/ratings/:user_id",
+            get(handlers::ratings::get_player_rating),
+        )
+        // Season routes
+        .route(
+            "/orgs/:org_id/seasons",
+            get(handlers::seasons::list_seasons).post(handlers::seasons::create_season),
+        )
+        .route(
+            "/orgs/:org_id/seasons/:season_id",
+            get(handlers::seasons::get_season).delete(handlers::seasons::delete_season),
+        )
+        // Member ban routes
+        .route(
+            "/orgs/:org_id/members/:member_id/ban",
+            post(handlers::members::ban_member).delete(handlers::members::unban_member),
+        )
+        .route("/orgs/:org_id/bans", get(handlers::members::list_bans))
+        // Org invite routes
+        .route(
+            "/orgs/:org_id/invites",
+            get(handlers::invites::list_invites).post(handlers::invites::create_invite),
+        )
+        .route(
+            "/orgs/:org_id/invites/:invite_id",
+            delete(handlers::invites::revoke_invite),
+        )
+        .route(
+            "/orgs/:org_id/invites/:invite_id/redemptions",
+            get(handlers::invites::list_invite_redemptions),
+        )
+        .route(
+            "/invites/:code/redeem",
+            post(handlers::invites::redeem_invite),
+        )
+        // Org API key routes
+        .route(
+            "/orgs/:org_id/api-keys",
+            get(handlers::api_keys::list_api_keys).post(handlers::api_keys::create_api_key),
+        )
+        .route(
+            "/orgs/:org_id/api-keys/:key_id",
+            delete(handlers::api_keys::revoke_api_key),
+        )
+        // Session routes
+        .route(
+            "/sessions",
+            get(handlers::sessions::list_sessions).delete(handlers::sessions::revoke_all_sessions),
+        )
+        .route("/sessions/:id", delete(handlers::sessions::revoke_session));
 
     let app = Router::new()
+        .route("/auth/discord/state", post(handle_discord_state))
+        .route("/auth/discord/login", get(handle_discord_login))
         .route("/auth/discord/callback", get(handle_discord_callback))
-        .route("/auth/discord/exchange", get(handle_discord_exchange))
+        .route("/auth/discord/exchange", post(handle_discord_exchange))
+        .route("/auth/logout", post(handle_logout))
         .route("/health", get(health_check))
         .route("/health/db", get(database_health_check))
         .nest("/api", api_routes)
@@ -118,48 +344,247 @@ async fn main() {
     let listener = tokio::net::TcpListener::bind(&bind_address).await.unwrap();
 
     println!("🚀 Server running on {}", Config::server_url());
-    axum::serve(listener, app).await.unwrap();
+    // Peer addresses are recorded on sessions
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
+}
+
+/// Issue a single-use OAuth state value bound to a PKCE challenge to send along to Discord
+async fn handle_discord_state(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(request): Json<DiscordStateRequest>,
+) -> impl IntoResponse {
+    if !auth::pkce::is_s256_challenge(&request.code_challenge) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "success": false,
+                "error": "invalid_code_challenge"
+            })),
+        )
+            .into_response();
+    }
+
+    let client_ip = auth::session::client_ip(&headers, peer);
+
+    match auth::oauth_state::issue_oauth_state(&state.db_pool, &request.code_challenge, &client_ip)
+        .await
+    {
+        Ok(oauth_state) => Json(serde_json::json!({
+            "success": true,
+            "state": oauth_state.state,
+            "expires_at": oauth_state.expires_at
+        }))
+        .into_response(),
+        Err(IssueStateError::TooManyPending) => (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(serde_json::json!({
+                "success": false,
+                "error": "too_many_pending_states"
+            })),
+        )
+            .into_response(),
+        Err(IssueStateError::Failed(e)) => {
+            println!("OAuth state generation failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "success": false,
+                    "error": "state_generation_failed"
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Start a login that Discord sends back to the backend callback: issue a state bound
+/// to a verifier kept in a cookie and redirect to Discord
+async fn handle_discord_login(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let code_verifier = auth::pkce::generate_code_verifier();
+    let code_challenge = auth::pkce::code_challenge_s256(&code_verifier);
+    let client_ip = auth::session::client_ip(&headers, peer);
+
+    let oauth_state =
+        match auth::oauth_state::issue_oauth_state(&state.db_pool, &code_challenge, &client_ip)
+            .await
+        {
+            Ok(oauth_state) => oauth_state,
+            Err(IssueStateError::TooManyPending) => {
+                return Redirect::to(&Config::oauth_error_redirect("too_many_pending_states"))
+                    .into_response();
+            }
+            Err(IssueStateError::Failed(e)) => {
+                println!("OAuth state generation failed: {}", e);
+                return Redirect::to(&Config::oauth_error_redirect("state_generation_failed"))
+                    .into_response();
+            }
+        };
+
+    match Config::discord_oauth_url(&oauth_state.state, &code_challenge) {
+        Ok(url) => (
+            [(
+                SET_COOKIE,
+                auth::oauth_state::verifier_cookie(&code_verifier),
+            )],
+            Redirect::to(&url),
+        )
+            .into_response(),
+        Err(e) => {
+            println!("{}", e);
+            Redirect::to(&Config::oauth_error_redirect("oauth_failed")).into_response()
+        }
+    }
 }
 
 #[axum::debug_handler]
 async fn handle_discord_callback(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Query(params): Query<DiscordCallbackQuery>,
 ) -> impl IntoResponse {
+    let clear_cookie = [(SET_COOKIE, auth::oauth_state::clear_verifier_cookie())];
+
     if let Some(error) = params.error {
         println!("Discord OAuth error: {}", error);
-        return Redirect::to(&Config::oauth_error_redirect("oauth_failed")).into_response();
+        return (
+            clear_cookie,
+            Redirect::to(&Config::oauth_error_redirect("oauth_failed")),
+        )
+            .into_response();
     }
 
-    if let Some(code) = params.code {
-        // Exchange authorization code for access token
-        match exchange_code_for_token_and_save_user(code, &state.db_pool).await {
-            Ok(user_id) => {
-                // Successfully authorized, redirect to home with user ID
-                Redirect::to(&Config::oauth_success_redirect(&user_id.to_string())).into_response()
-            }
-            Err(e) => {
-                println!("Token exchange failed: {}", e);
-                Redirect::to(&Config::oauth_error_redirect("token_exchange_failed")).into_response()
-            }
+    // Only the browser that started the login at `/auth/discord/login` holds the verifier
+    let code_verifier = auth::oauth_state::verifier_from_cookies(&headers).unwrap_or_default();
+    if let Err(e) = auth::oauth_state::verify_oauth_state(
+        &state.db_pool,
+        params.state.as_deref(),
+        &code_verifier,
+    )
+    .await
+    {
+        println!("OAuth state rejected: {}", e);
+        return (
+            clear_cookie,
+            Redirect::to(&Config::oauth_error_redirect("invalid_state")),
+        )
+            .into_response();
+    }
+
+    let Some(code) = params.code else {
+        return (
+            clear_cookie,
+            Redirect::to(&Config::oauth_error_redirect("missing_code")),
+        )
+            .into_response();
+    };
+
+    // Exchange authorization code for access token
+    let session = match exchange_code_for_token_and_save_user(
+        code,
+        Some(code_verifier),
+        &state.db_pool,
+    )
+    .await
+    {
+        Ok(user_id) => {
+            let device = SessionDevice::from_request(&headers, peer);
+            auth::session::issue_session_token(&state.db_pool, user_id, device).await
+        }
+        Err(e) => Err(e),
+    };
+
+    match session {
+        // Successfully authorized, redirect to home with a signed session token
+        Ok(session) => (
+            clear_cookie,
+            Redirect::to(&Config::oauth_success_redirect(&session.token)),
+        )
+            .into_response(),
+        Err(e) => {
+            println!("Token exchange failed: {}", e);
+            (
+                clear_cookie,
+                Redirect::to(&Config::oauth_error_redirect("token_exchange_failed")),
+            )
+                .into_response()
         }
-    } else {
-        Redirect::to(&Config::oauth_error_redirect("missing_code")).into_response()
     }
 }
 
 #[axum::debug_handler]
 async fn handle_discord_exchange(
     State(state): State<AppState>,
-    Query(params): Query<DiscordExchangeQuery>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(params): Json<DiscordExchangeRequest>,
 ) -> impl IntoResponse {
+    let Some(code_verifier) = params.code_verifier else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "success": false,
+                "error": "missing_code_verifier"
+            })),
+        )
+            .into_response();
+    };
+
+    if let Err(e) = auth::oauth_state::verify_oauth_state(
+        &state.db_pool,
+        params.state.as_deref(),
+        &code_verifier,
+    )
+    .await
+    {
+        println!("OAuth state rejected: {}", e);
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "success": false,
+                "error": "invalid_state",
+                "details": e
+            })),
+        )
+            .into_response();
+    }
+
     // Exchange authorization code for access token
-    match exchange_code_for_token_and_save_user(params.code, &state.db_pool).await {
+    let session = match exchange_code_for_token_and_save_user(
+        params.code,
+        Some(code_verifier),
+        &state.db_pool,
+    )
+    .await
+    {
         Ok(user_id) => {
-            // Return JSON with user_id
+            let device = SessionDevice::from_request(&headers, peer);
+            auth::session::issue_session_token(&state.db_pool, user_id, device)
+                .await
+                .map(|session| (user_id, session))
+        }
+        Err(e) => Err(e),
+    };
+
+    match session {
+        Ok((user_id, session)) => {
+            // Return JSON with user_id and the signed session token
             Json(serde_json::json!({
                 "success": true,
-                "user_id": user_id.to_string()
+                "user_id": user_id.to_string(),
+                "session_token": session.token,
+                "expires_at": session.expires_at
             }))
             .into_response()
         }
@@ -175,8 +600,73 @@ async fn handle_discord_exchange(
     }
 }
 
+/// End the caller's session and revoke their Discord token
+///
+/// The stored token is deleted and the session invalidated even when Discord
+/// can't be reached, so logging out always succeeds locally.
+async fn handle_logout(State(state): State<AppState>, auth: AuthUser) -> impl IntoResponse {
+    let mut discord_token_revoked = false;
+
+    match database::queries::get_discord_token_by_user_id(&state.db_pool, auth.user_id).await {
+        Ok(Some(token)) => {
+            // Revoking the refresh token also kills access tokens issued from it
+            let revocation = match token.refresh_token.as_deref() {
+                Some(refresh_token) => {
+                    discord_api::revoke_token(refresh_token, "refresh_token").await
+                }
+                None => discord_api::revoke_token(&token.access_token, "access_token").await,
+            };
+
+            match revocation {
+                Ok(()) => discord_token_revoked = true,
+                Err(e) => println!(
+                    "Discord token revocation for user_id {} failed: {}",
+                    auth.user_id, e
+                ),
+            }
+
+            if let Err(e) =
+                database::queries::delete_discord_token_by_user_id(&state.db_pool, auth.user_id)
+                    .await
+            {
+                println!("Failed to delete Discord token on logout: {}", e);
+                return logout_failed();
+            }
+        }
+        Ok(None) => {}
+        Err(e) => {
+            println!("Failed to load Discord token on logout: {}", e);
+            return logout_failed();
+        }
+    }
+
+    if let Err(e) = database::queries::revoke_session(&state.db_pool, auth.session_id).await {
+        println!("Failed to revoke session on logout: {}", e);
+        return logout_failed();
+    }
+
+    println!("User {} logged out", auth.user_id);
+    Json(serde_json::json!({
+        "success": true,
+        "discord_token_revoked": discord_token_revoked
+    }))
+    .into_response()
+}
+
+fn logout_failed() -> axum::response::Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::json!({
+            "success": false,
+            "error": "logout_failed"
+        })),
+    )
+        .into_response()
+}
+
 async fn exchange_code_for_token_and_save_user(
     code: String,
+    code_verifier: Option<String>,
     pool: &database::DatabasePool,
 ) -> Result<uuid::Uuid, String> {
     let client_id = Config::discord_client_id();
@@ -190,6 +680,9 @@ async fn exchange_code_for_token_and_save_user(
     params.insert("grant_type", "authorization_code".to_string());
     params.insert("code", code);
     params.insert("redirect_uri", redirect_uri);
+    if let Some(code_verifier) = code_verifier {
+        params.insert("code_verifier", code_verifier);
+    }
 
     let client = reqwest::Client::new();
     let response = client
@@ -354,9 +847,16 @@ async fn create_or_update_user_from_discord(
             bio: None,
         };
 
+        // A deleted account keeps its Discord ID until it is purged
         let new_user = database::queries::create_user(pool, create_user)
             .await
-            .map_err(|e| format!("Failed to create user: {}", e))?;
+            .map_err(|e| {
+                if e.to_string().contains("duplicate key") {
+                    "This account has been deleted".to_string()
+                } else {
+                    format!("Failed to create user: {}", e)
+                }
+            })?;
 
         Ok(new_user.id)
     }
@@ -0,0 +1,27 @@
+//! Periodically lifts member bans whose `expires_at` has passed
+//!
+//! Members whose ban expired are restored to `Spectating`; permanent bans are
+//! left alone until someone unbans the member.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::database::{DatabasePool, queries};
+
+/// Spawn the sweep loop on the Tokio runtime
+pub fn spawn(pool: Arc<DatabasePool>) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(Config::ban_sweep_interval_seconds()));
+
+        loop {
+            interval.tick().await;
+            match queries::lift_expired_bans(&pool).await {
+                Ok(0) => {}
+                Ok(lifted) => println!("Lifted {} expired member ban(s)", lifted),
+                Err(e) => println!("Expired ban sweep failed: {}", e),
+            }
+        }
+    });
+}
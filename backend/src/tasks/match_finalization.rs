@@ -0,0 +1,28 @@
+//! Periodically finalizes reported matches nobody disputed
+//!
+//! A pending match whose `confirm_deadline` has passed counts as confirmed by every
+//! participant who didn't answer. Disputed matches wait for an org admin instead.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::database::{DatabasePool, queries};
+
+/// Spawn the finalization loop on the Tokio runtime
+pub fn spawn(pool: Arc<DatabasePool>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            Config::match_finalize_interval_seconds(),
+        ));
+
+        loop {
+            interval.tick().await;
+            match queries::finalize_expired_matches(&pool).await {
+                Ok(0) => {}
+                Ok(finalized) => println!("Finalized {} unconfirmed match(es)", finalized),
+                Err(e) => println!("Match finalization sweep failed: {}", e),
+            }
+        }
+    });
+}
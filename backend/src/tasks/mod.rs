@@ -0,0 +1,7 @@
+//! Background tasks spawned alongside the HTTP server
+
+pub mod ban_expiry;
+pub mod match_finalization;
+pub mod purge;
+pub mod season_rollover;
+pub mod token_refresh;
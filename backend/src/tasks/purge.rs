@@ -0,0 +1,36 @@
+//! Periodically hard-deletes soft-deleted users and orgs
+//!
+//! Rows deleted more than `Config::soft_delete_retention_days()` ago can no longer
+//! be restored and are removed for good, together with everything that cascades
+//! from them. Users with match history are anonymized instead, see
+//! [`queries::purge_deleted`].
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::config::Config;
+use crate::database::{DatabasePool, queries};
+
+/// Spawn the purge loop on the Tokio runtime
+pub fn spawn(pool: Arc<DatabasePool>) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(Config::purge_interval_seconds()));
+
+        loop {
+            interval.tick().await;
+            match queries::purge_deleted(&pool, retention_cutoff()).await {
+                Ok(0) => {}
+                Ok(purged) => println!("Purged {} deleted user(s) and org(s)", purged),
+                Err(e) => println!("Purge of deleted rows failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Rows deleted before this time are past the retention window and can't be restored
+pub fn retention_cutoff() -> chrono::DateTime<Utc> {
+    Utc::now() - chrono::Duration::days(Config::soft_delete_retention_days())
+}
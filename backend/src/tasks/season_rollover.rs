@@ -0,0 +1,29 @@
+//! Periodically rolls over seasons that have ended
+//!
+//! Rolling over freezes a season's final standings and pulls the org's ratings back
+//! towards the default by the season's `soft_reset`. A season waits until every match
+//! that ended during it has been finalized or voided, so late confirmations still count.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::database::{DatabasePool, queries};
+
+/// Spawn the rollover loop on the Tokio runtime
+pub fn spawn(pool: Arc<DatabasePool>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            Config::season_rollover_interval_seconds(),
+        ));
+
+        loop {
+            interval.tick().await;
+            match queries::roll_over_ended_seasons(&pool).await {
+                Ok(0) => {}
+                Ok(rolled_over) => println!("Rolled over {} ended season(s)", rolled_over),
+                Err(e) => println!("Season rollover sweep failed: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,97 @@
+//! Periodically renews Discord access tokens that are close to expiry
+//!
+//! Tokens expiring within `Config::token_refresh_window_seconds()` are refreshed
+//! with their stored refresh token. When Discord rejects the refresh the token is
+//! marked invalid, so the user has to sign in with Discord again.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use shared::database::{DbDiscordToken, UpdateDiscordToken};
+
+use crate::config::Config;
+use crate::database::{DatabasePool, queries};
+use crate::discord_api::{self, DiscordApiError};
+
+/// Spawn the refresh loop on the Tokio runtime
+pub fn spawn(pool: Arc<DatabasePool>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            Config::token_refresh_interval_seconds(),
+        ));
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = refresh_expiring_tokens(&pool).await {
+                println!("Discord token refresh failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Refresh every usable token that expires within the refresh window. A token that
+/// can't be decrypted or saved is logged and skipped, so it doesn't hold up the others.
+async fn refresh_expiring_tokens(pool: &DatabasePool) -> Result<(), sqlx::Error> {
+    let cutoff = Utc::now() + chrono::Duration::seconds(Config::token_refresh_window_seconds());
+    let tokens = queries::get_discord_tokens_expiring_before(pool, cutoff).await?;
+
+    for (user_id, token) in tokens {
+        let token = match token {
+            Ok(token) => token,
+            Err(e) => {
+                println!(
+                    "Skipping unreadable Discord token for user_id {}: {}",
+                    user_id, e
+                );
+                continue;
+            }
+        };
+
+        if let Err(e) = refresh_token(pool, token).await {
+            println!(
+                "Discord token refresh for user_id {} failed: {}",
+                user_id, e
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn refresh_token(pool: &DatabasePool, token: DbDiscordToken) -> Result<(), sqlx::Error> {
+    let Some(refresh_token) = token.refresh_token.as_deref() else {
+        return Ok(());
+    };
+
+    match discord_api::refresh_access_token(refresh_token).await {
+        Ok(grant) => {
+            let update_token = UpdateDiscordToken {
+                access_token: Some(grant.access_token),
+                refresh_token: grant.refresh_token,
+                token_type: grant.token_type,
+                scope: grant.scope,
+                expires_at: grant
+                    .expires_in
+                    .map(|seconds| Utc::now() + chrono::Duration::seconds(seconds)),
+            };
+            queries::update_discord_token(pool, token.user_id, update_token).await?;
+            println!("Refreshed Discord token for user_id: {}", token.user_id);
+        }
+        Err(DiscordApiError::Rejected(e)) => {
+            queries::invalidate_discord_token(pool, token.user_id).await?;
+            println!(
+                "Discord rejected token refresh for user_id {}, marked invalid: {}",
+                token.user_id, e
+            );
+        }
+        Err(e @ DiscordApiError::Transient(_)) => {
+            println!(
+                "Discord token refresh for user_id {} will be retried: {}",
+                token.user_id, e
+            );
+        }
+    }
+
+    Ok(())
+}
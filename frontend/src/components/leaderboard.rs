@@ -1,56 +1,161 @@
+use crate::services::api::{ApiService, LeaderboardEntry};
+use crate::services::DiscordService;
 use dioxus::prelude::*;
 
-#[derive(Clone, PartialEq)]
-pub struct Player {
-    pub discord_name: String,
-    pub win_rate: f32,
-    pub currently_playing_field: String,
-}
-
+/// An org's leaderboard for one game type, with pickers for the org and game and one
+/// to look back at the final standings of archived seasons
 #[component]
-pub fn Leaderboard() -> Element {
-    let players = use_signal(|| {
-        vec![
-            Player {
-                discord_name: "Player#1234".to_string(),
-                win_rate: 75.5,
-                currently_playing_field: "Field A".to_string(),
-            },
-            Player {
-                discord_name: "Gamer#5678".to_string(),
-                win_rate: 68.2,
-                currently_playing_field: "Field B".to_string(),
-            },
-            Player {
-                discord_name: "Pro#9999".to_string(),
-                win_rate: 82.1,
-                currently_playing_field: "Field C".to_string(),
-            },
-        ]
+pub fn Leaderboard(org_id: Option<String>, game_type: Option<String>) -> Element {
+    // The props only seed the pickers; `None` season shows the live ratings of the
+    // current season
+    let mut selected_org = use_signal(|| org_id.clone());
+    let mut selected_game = use_signal(|| game_type.clone());
+    let mut selected_season = use_signal(|| None::<String>);
+
+    // Start over whenever the parent points us at another board
+    use_effect(use_reactive!(|(org_id, game_type)| {
+        selected_org.set(org_id);
+        selected_game.set(game_type);
+        selected_season.set(None);
+    }));
+
+    // The orgs the signed-in user can pick from
+    let memberships = use_resource(|| async move {
+        match DiscordService::get_stored_auth_state() {
+            Some((user_id, _, _)) => ApiService::get_memberships(&user_id).await,
+            None => Ok(Vec::new()),
+        }
+    });
+
+    let seasons = use_resource(move || {
+        let org_id = selected_org();
+        async move {
+            match org_id {
+                Some(org_id) => ApiService::get_seasons(&org_id).await,
+                None => Ok(Vec::new()),
+            }
+        }
     });
 
+    let has_board = selected_org().is_some() && selected_game().is_some();
+    let entries = use_resource(move || {
+        let org_id = selected_org();
+        let game_type = selected_game();
+        let season_id = selected_season();
+        async move {
+            match (org_id, game_type) {
+                (Some(org_id), Some(game_type)) => {
+                    ApiService::get_leaderboard(&org_id, &game_type, season_id.as_deref()).await
+                }
+                _ => Ok(Vec::new()),
+            }
+        }
+    });
+
+    let orgs: Vec<_> = match &*memberships.read() {
+        Some(Ok(memberships)) => memberships
+            .iter()
+            .map(|membership| membership.discord_org.clone())
+            .collect(),
+        _ => Vec::new(),
+    };
+    // An org handed in by the page may not be one of ours; still show it as picked
+    let unlisted_org = selected_org().filter(|org_id| !orgs.iter().any(|org| &org.id == org_id));
+
+    // Only archived seasons have standings of their own
+    let archived_seasons: Vec<_> = match &*seasons.read() {
+        Some(Ok(seasons)) => seasons
+            .iter()
+            .filter(|season| season.archived_at.is_some())
+            .cloned()
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let players: Vec<LeaderboardEntry> = match &*entries.read() {
+        Some(Ok(entries)) => entries.clone(),
+        _ => Vec::new(),
+    };
+    let error = match &*entries.read() {
+        Some(Err(e)) => Some(e.clone()),
+        _ => None,
+    };
+
     rsx! {
         div { class: "leaderboard-container",
             h2 { class: "leaderboard-title", "🏆 Leaderboard" }
 
+            div { class: "leaderboard-picker",
+                select {
+                    id: "org-select",
+                    onchange: move |evt| {
+                        let org_id = evt.value();
+                        selected_org.set((!org_id.is_empty()).then_some(org_id));
+                        selected_season.set(None);
+                    },
+                    option { value: "", selected: selected_org().is_none(), "Organization…" }
+                    if let Some(org_id) = unlisted_org {
+                        option { value: "{org_id}", selected: true, "{org_id}" }
+                    }
+                    for org in orgs {
+                        option {
+                            value: "{org.id}",
+                            selected: selected_org().as_deref() == Some(org.id.as_str()),
+                            "{org.name}"
+                        }
+                    }
+                }
+                input {
+                    r#type: "text",
+                    placeholder: "Game type, e.g. chess",
+                    value: selected_game().unwrap_or_default(),
+                    onchange: move |evt| {
+                        let game_type = evt.value().trim().to_lowercase();
+                        selected_game.set((!game_type.is_empty()).then_some(game_type));
+                        selected_season.set(None);
+                    },
+                }
+            }
+
+            if !archived_seasons.is_empty() {
+                div { class: "season-picker",
+                    label { r#for: "season-select", "Season" }
+                    select {
+                        id: "season-select",
+                        onchange: move |evt| {
+                            let season_id = evt.value();
+                            selected_season.set((!season_id.is_empty()).then_some(season_id));
+                        },
+                        option { value: "", selected: selected_season().is_none(), "Current" }
+                        for season in archived_seasons {
+                            option {
+                                value: "{season.id}",
+                                selected: selected_season().as_deref() == Some(season.id.as_str()),
+                                "{season.name}"
+                            }
+                        }
+                    }
+                }
+            }
+
             div { class: "leaderboard-table-container",
                 table { class: "leaderboard-table",
                     thead {
                         tr {
                             th { "Rank" }
                             th { "Discord Name" }
-                            th { "Win Rate" }
-                            th { "Currently Playing Field" }
+                            th { "Rating" }
+                            th { "Games" }
                         }
                     }
                     tbody {
-                        {players().iter().enumerate().map(|(index, player)| {
+                        {players.iter().map(|entry| {
                             rsx! {
                                 tr { class: "leaderboard-row",
-                                    td { class: "rank-cell", "{index + 1}" }
-                                    td { class: "name-cell", "{player.discord_name}" }
-                                    td { class: "winrate-cell", "{player.win_rate:.1}%" }
-                                    td { class: "field-cell", "{player.currently_playing_field}" }
+                                    td { class: "rank-cell", "{entry.rank}" }
+                                    td { class: "name-cell", "{entry.user.display_name}" }
+                                    td { class: "rating-cell", "{entry.rating.rating:.0} ±{entry.rating.deviation:.0}" }
+                                    td { class: "games-cell", "{entry.rating.games_played}" }
                                 }
                             }
                         })}
@@ -58,7 +163,15 @@ pub fn Leaderboard() -> Element {
                 }
             }
 
-            if players().is_empty() {
+            if let Some(error) = error {
+                div { class: "leaderboard-error",
+                    p { "Couldn't load the leaderboard: {error}" }
+                }
+            } else if !has_board {
+                div { class: "empty-leaderboard",
+                    p { "Pick an organization and game to see its leaderboard." }
+                }
+            } else if players.is_empty() && entries.read().is_some() {
                 div { class: "empty-leaderboard",
                     p { "No players on the leaderboard yet. Be the first to compete!" }
                 }
@@ -17,9 +17,9 @@ impl Config {
         option_env!("DISCORD_REDIRECT_URI").unwrap_or("http://localhost:8081/auth/discord/callback")
     }
 
-    /// Discord OAuth scopes
+    /// Discord OAuth scopes (`guilds` lets users import servers they manage as orgs)
     pub fn discord_scopes() -> &'static str {
-        "identify"
+        "identify guilds"
     }
 
     /// Backend base URL
@@ -52,24 +52,33 @@ impl Config {
         option_env!("NODE_ENV").unwrap_or("development")
     }
 
-    /// Get the full Discord OAuth authorization URL
-    pub fn discord_oauth_url() -> String {
+    /// Get the full Discord OAuth authorization URL for a backend-issued state value
+    /// and a PKCE S256 code challenge
+    pub fn discord_oauth_url(state: &str, code_challenge: &str) -> String {
         format!(
-            "{}?client_id={}&response_type=code&redirect_uri={}&scope={}",
+            "{}?client_id={}&response_type=code&redirect_uri={}&scope={}&state={}&code_challenge={}&code_challenge_method=S256",
             Self::discord_oauth_base_url(),
             Self::discord_client_id(),
             urlencoding::encode(Self::discord_redirect_uri()),
-            Self::discord_scopes()
+            urlencoding::encode(Self::discord_scopes()),
+            urlencoding::encode(state),
+            urlencoding::encode(code_challenge)
         )
     }
 
-    /// Get the backend Discord exchange URL with code
-    pub fn backend_discord_exchange_url(code: &str) -> String {
-        format!(
-            "{}/discord/exchange?code={}",
-            Self::backend_auth_url(),
-            code
-        )
+    /// Get the backend URL that issues OAuth state values
+    pub fn backend_discord_state_url() -> String {
+        format!("{}/discord/state", Self::backend_auth_url())
+    }
+
+    /// Get the backend URL that ends the current session
+    pub fn backend_logout_url() -> String {
+        format!("{}/logout", Self::backend_auth_url())
+    }
+
+    /// Get the backend Discord exchange URL (the code, state and PKCE code verifier go in the body)
+    pub fn backend_discord_exchange_url() -> String {
+        format!("{}/discord/exchange", Self::backend_auth_url())
     }
 
     /// Get a backend API endpoint URL
@@ -138,17 +147,25 @@ mod tests {
 
     #[test]
     fn test_discord_oauth_url() {
-        let url = Config::discord_oauth_url();
+        let url = Config::discord_oauth_url("test_state", "test_challenge");
         assert!(url.contains("discord.com/oauth2/authorize"));
         assert!(url.contains("client_id="));
         assert!(url.contains("response_type=code"));
-        assert!(url.contains("scope=identify"));
+        assert!(url.contains("scope=identify%20guilds"));
+        assert!(url.contains("state=test_state"));
+        assert!(url.contains("code_challenge=test_challenge&code_challenge_method=S256"));
     }
 
     #[test]
     fn test_backend_endpoints() {
-        let exchange_url = Config::backend_discord_exchange_url("test_code");
-        assert!(exchange_url.contains("/discord/exchange?code=test_code"));
+        let exchange_url = Config::backend_discord_exchange_url();
+        assert!(exchange_url.ends_with("/auth/discord/exchange"));
+
+        let state_url = Config::backend_discord_state_url();
+        assert!(state_url.ends_with("/auth/discord/state"));
+
+        let logout_url = Config::backend_logout_url();
+        assert!(logout_url.ends_with("/auth/logout"));
 
         let api_url = Config::backend_api_endpoint("users");
         assert!(api_url.contains("/api/users"));
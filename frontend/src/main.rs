@@ -29,8 +29,9 @@ enum Route {
         // the component for that route will be rendered. The component name that is rendered defaults to the variant name.
         #[route("/")]
         Home {},
-        #[route("/leaderboard")]
-        LeaderboardPage {},
+        // Query parameters select whose leaderboard to show, e.g. `/leaderboard?org=...&game=chess`
+        #[route("/leaderboard?:org&:game")]
+        LeaderboardPage { org: Option<String>, game: Option<String> },
         // The route attribute can include dynamic parameters that implement [`std::str::FromStr`] and [`std::fmt::Display`] with the `:` syntax.
         // In this case, id will match any integer like `/blog/123` or `/blog/-456`.
         #[route("/blog/:id")]
@@ -38,8 +39,8 @@ enum Route {
         // an `id` prop of type `i32`.
         Blog { id: i32 },
     // Discord OAuth callback route (no layout) - capture query parameters
-    #[route("/auth/discord/callback?:code&:error")]
-    DiscordCallback { code: Option<String>, error: Option<String> },
+    #[route("/auth/discord/callback?:code&:error&:state")]
+    DiscordCallback { code: Option<String>, error: Option<String>, state: Option<String> },
 }
 
 // We can import assets in dioxus with the `asset!` macro. This macro takes a path to an asset relative to the crate root.
@@ -1,3 +1,4 @@
+use crate::services::DiscordService;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
@@ -14,6 +15,62 @@ pub struct User {
     pub updated_at: String,
 }
 
+/// Envelope the backend wraps most responses in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub data: Option<T>,
+    pub error: Option<String>,
+}
+
+impl<T> ApiResponse<T> {
+    /// Unwrap the data, or the backend's error message
+    fn into_result(self) -> Result<T, String> {
+        match self.data {
+            Some(data) if self.success => Ok(data),
+            _ => Err(self.error.unwrap_or_else(|| "Unknown error".to_string())),
+        }
+    }
+}
+
+/// A dated competitive season of an org; `archived_at` is set once it has rolled over
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Season {
+    pub id: String,
+    pub name: String,
+    pub starts_at: String,
+    pub ends_at: String,
+    pub soft_reset: f64,
+    pub archived_at: Option<String>,
+}
+
+/// The org side of a membership, as much of it as the pickers need
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrgSummary {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Membership {
+    pub discord_org: OrgSummary,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerRating {
+    pub game_type: String,
+    pub rating: f64,
+    pub deviation: f64,
+    pub games_played: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub rank: i64,
+    pub user: User,
+    pub rating: PlayerRating,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ApiError {
     pub error: String,
@@ -25,6 +82,18 @@ pub struct ApiService;
 impl ApiService {
     const BASE_URL: &'static str = "http://localhost:8080/api";
 
+    /// Build request headers, attaching the session token when logged in
+    fn headers() -> Result<Headers, JsValue> {
+        let headers = Headers::new()?;
+        headers.set("Content-Type", "application/json")?;
+
+        if let Some(session_token) = DiscordService::get_session_token() {
+            headers.set("Authorization", &format!("Bearer {}", session_token))?;
+        }
+
+        Ok(headers)
+    }
+
     /// Make a GET request to the API
     pub async fn get(endpoint: &str) -> Result<String, JsValue> {
         let url = format!("{}{}", Self::BASE_URL, endpoint);
@@ -32,8 +101,7 @@ impl ApiService {
         let opts = RequestInit::new();
         opts.set_method("GET");
 
-        let headers = Headers::new()?;
-        headers.set("Content-Type", "application/json")?;
+        let headers = Self::headers()?;
         opts.set_headers(&headers);
 
         let request = Request::new_with_str_and_init(&url, &opts)?;
@@ -55,8 +123,7 @@ impl ApiService {
         opts.set_method("POST");
         opts.set_body(&JsValue::from_str(body));
 
-        let headers = Headers::new()?;
-        headers.set("Content-Type", "application/json")?;
+        let headers = Self::headers()?;
         opts.set_headers(&headers);
 
         let request = Request::new_with_str_and_init(&url, &opts)?;
@@ -104,6 +171,58 @@ impl ApiService {
         }
     }
 
+    /// Get the orgs a user is a member of (only allowed for the signed-in user)
+    pub async fn get_memberships(user_id: &str) -> Result<Vec<Membership>, String> {
+        let endpoint = format!("/users/{}/memberships", user_id);
+
+        match Self::get(&endpoint).await {
+            Ok(response_text) => {
+                serde_json::from_str::<ApiResponse<Vec<Membership>>>(&response_text)
+                    .map_err(|e| format!("Failed to parse memberships: {}", e))?
+                    .into_result()
+            }
+            Err(e) => Err(format!("Failed to fetch memberships: {:?}", e)),
+        }
+    }
+
+    /// Get an org's seasons, latest first
+    pub async fn get_seasons(org_id: &str) -> Result<Vec<Season>, String> {
+        let endpoint = format!("/orgs/{}/seasons", org_id);
+
+        match Self::get(&endpoint).await {
+            Ok(response_text) => serde_json::from_str::<ApiResponse<Vec<Season>>>(&response_text)
+                .map_err(|e| format!("Failed to parse seasons: {}", e))?
+                .into_result(),
+            Err(e) => Err(format!("Failed to fetch seasons: {:?}", e)),
+        }
+    }
+
+    /// Get an org's leaderboard for one game type; with a season, that season's
+    /// archived standings
+    pub async fn get_leaderboard(
+        org_id: &str,
+        game_type: &str,
+        season_id: Option<&str>,
+    ) -> Result<Vec<LeaderboardEntry>, String> {
+        let mut endpoint = format!(
+            "/orgs/{}/leaderboard?game_type={}",
+            org_id,
+            String::from(js_sys::encode_uri_component(game_type))
+        );
+        if let Some(season_id) = season_id {
+            endpoint.push_str(&format!("&season={}", season_id));
+        }
+
+        match Self::get(&endpoint).await {
+            Ok(response_text) => {
+                serde_json::from_str::<ApiResponse<Vec<LeaderboardEntry>>>(&response_text)
+                    .map_err(|e| format!("Failed to parse leaderboard: {}", e))?
+                    .into_result()
+            }
+            Err(e) => Err(format!("Failed to fetch leaderboard: {:?}", e)),
+        }
+    }
+
     /// Check health of the API
     pub async fn health_check() -> Result<HealthResponse, String> {
         match Self::get("/health").await {
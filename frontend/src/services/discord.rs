@@ -1,7 +1,9 @@
 use crate::config::Config;
+use crate::services::pkce;
 use serde::{Deserialize, Serialize};
-use wasm_bindgen::JsValue;
-use web_sys::{window, Url};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{window, Headers, Request, RequestInit, Response};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DiscordUser {
@@ -17,9 +19,16 @@ pub struct DiscordUser {
 pub struct DiscordService;
 
 impl DiscordService {
-    /// Generate Discord OAuth URL and redirect user to Discord
-    pub fn start_oauth_flow() -> Result<(), JsValue> {
-        let oauth_url = Config::discord_oauth_url();
+    /// Generate a PKCE verifier, request an OAuth state bound to its challenge from the
+    /// backend, remember both, and redirect user to Discord
+    pub async fn start_oauth_flow() -> Result<(), JsValue> {
+        let code_verifier = pkce::generate_code_verifier()?;
+        let code_challenge = pkce::code_challenge_s256(&code_verifier);
+
+        let state = Self::fetch_oauth_state(&code_challenge).await?;
+        Self::store_oauth_flow(&state, &code_verifier)?;
+
+        let oauth_url = Config::discord_oauth_url(&state, &code_challenge);
 
         // Log the exact OAuth URL we're using
         web_sys::console::log_1(&"🔗 OAUTH URL BEING USED:".into());
@@ -43,6 +52,60 @@ impl DiscordService {
         Ok(())
     }
 
+    /// Ask the backend for a fresh single-use OAuth state value bound to a PKCE challenge
+    async fn fetch_oauth_state(code_challenge: &str) -> Result<String, JsValue> {
+        let body = serde_json::json!({ "code_challenge": code_challenge }).to_string();
+
+        let opts = RequestInit::new();
+        opts.set_method("POST");
+        opts.set_body(&JsValue::from_str(&body));
+
+        let headers = Headers::new()?;
+        headers.set("Content-Type", "application/json")?;
+        opts.set_headers(&headers);
+
+        let request = Request::new_with_str_and_init(&Config::backend_discord_state_url(), &opts)?;
+
+        let window = window().ok_or("No window object")?;
+        let resp_value = JsFuture::from(window.fetch_with_request(&request)).await?;
+        let resp: Response = resp_value.dyn_into()?;
+
+        if !resp.ok() {
+            return Err(format!("Failed to get OAuth state: {}", resp.status()).into());
+        }
+
+        let text = JsFuture::from(resp.text()?).await?;
+        let json = serde_json::from_str::<serde_json::Value>(&text.as_string().unwrap_or_default())
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse OAuth state: {}", e)))?;
+
+        json.get("state")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| "No state in backend response".into())
+    }
+
+    /// Remember the OAuth state and PKCE verifier for the duration of this browser tab
+    fn store_oauth_flow(state: &str, code_verifier: &str) -> Result<(), JsValue> {
+        if let Some(window) = window() {
+            if let Ok(Some(storage)) = window.session_storage() {
+                storage.set_item("oauth_state", state)?;
+                storage.set_item("oauth_code_verifier", code_verifier)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Take the OAuth state and PKCE verifier stored by `start_oauth_flow`, removing them
+    /// so they can't be reused
+    pub fn take_oauth_flow() -> Option<(String, String)> {
+        let storage = window()?.session_storage().ok().flatten()?;
+        let state = storage.get_item("oauth_state").ok().flatten();
+        let code_verifier = storage.get_item("oauth_code_verifier").ok().flatten();
+        let _ = storage.remove_item("oauth_state");
+        let _ = storage.remove_item("oauth_code_verifier");
+        state.zip(code_verifier)
+    }
+
     /// Clear URL parameters by replacing current state
     pub fn clear_url_params() -> Result<(), JsValue> {
         if let Some(window) = window() {
@@ -70,12 +133,14 @@ impl DiscordService {
     /// Store authentication state in localStorage
     pub fn store_auth_state(
         user_id: &str,
+        session_token: &str,
         username: &str,
         avatar_url: Option<String>,
     ) -> Result<(), JsValue> {
         if let Some(window) = window() {
             if let Ok(Some(storage)) = window.local_storage() {
                 storage.set_item("discord_user_id", user_id)?;
+                storage.set_item("session_token", session_token)?;
                 storage.set_item("discord_username", username)?;
 
                 if let Some(avatar) = avatar_url {
@@ -95,8 +160,9 @@ impl DiscordService {
         if let Some(window) = window() {
             if let Ok(Some(storage)) = window.local_storage() {
                 let is_logged_in = storage.get_item("is_logged_in").ok().flatten();
+                let session_token = storage.get_item("session_token").ok().flatten();
 
-                if is_logged_in.as_deref() == Some("true") {
+                if is_logged_in.as_deref() == Some("true") && session_token.is_some() {
                     let user_id = storage.get_item("discord_user_id").ok().flatten();
                     let username = storage.get_item("discord_username").ok().flatten();
                     let avatar_url = storage.get_item("discord_avatar_url").ok().flatten();
@@ -110,11 +176,61 @@ impl DiscordService {
         None
     }
 
+    /// Retrieve the stored session token, if any
+    pub fn get_session_token() -> Option<String> {
+        window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .and_then(|storage| storage.get_item("session_token").ok().flatten())
+    }
+
+    /// Read the user ID (`sub` claim) from a session token issued by the backend.
+    /// The signature is not checked here; the backend verifies it on every request.
+    pub fn user_id_from_session_token(session_token: &str) -> Option<String> {
+        let payload = session_token.split('.').nth(1)?;
+
+        // Convert base64url to standard base64 for `atob`
+        let mut encoded = payload.replace('-', "+").replace('_', "/");
+        while encoded.len() % 4 != 0 {
+            encoded.push('=');
+        }
+
+        let decoded = window()?.atob(&encoded).ok()?;
+        let claims = serde_json::from_str::<serde_json::Value>(&decoded).ok()?;
+        claims.get("sub")?.as_str().map(|s| s.to_string())
+    }
+
+    /// Ask the backend to end the session and revoke the Discord token
+    pub async fn logout() -> Result<(), JsValue> {
+        let Some(session_token) = Self::get_session_token() else {
+            return Ok(());
+        };
+
+        let opts = RequestInit::new();
+        opts.set_method("POST");
+
+        let headers = Headers::new()?;
+        headers.set("Authorization", &format!("Bearer {}", session_token))?;
+        opts.set_headers(&headers);
+
+        let request = Request::new_with_str_and_init(&Config::backend_logout_url(), &opts)?;
+
+        let window = window().ok_or("No window object")?;
+        let resp_value = JsFuture::from(window.fetch_with_request(&request)).await?;
+        let resp: Response = resp_value.dyn_into()?;
+
+        if !resp.ok() {
+            return Err(format!("Logout failed: {}", resp.status()).into());
+        }
+
+        Ok(())
+    }
+
     /// Clear stored authentication state
     pub fn clear_stored_auth_state() -> Result<(), JsValue> {
         if let Some(window) = window() {
             if let Ok(Some(storage)) = window.local_storage() {
                 storage.remove_item("discord_user_id")?;
+                storage.remove_item("session_token")?;
                 storage.remove_item("discord_username")?;
                 storage.remove_item("discord_avatar_url")?;
                 storage.remove_item("is_logged_in")?;
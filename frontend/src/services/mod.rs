@@ -1,4 +1,5 @@
 pub mod api;
 pub mod discord;
+pub mod pkce;
 
 pub use discord::*;
@@ -0,0 +1,35 @@
+//! PKCE (RFC 7636) code verifier and challenge generation for the Discord OAuth flow
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use sha2::{Digest, Sha256};
+use wasm_bindgen::JsValue;
+
+/// Generate a random code verifier (32 random bytes, base64url encoded to 43 characters)
+pub fn generate_code_verifier() -> Result<String, JsValue> {
+    let mut bytes = [0u8; 32];
+    web_sys::window()
+        .ok_or("No window object")?
+        .crypto()?
+        .get_random_values_with_u8_array(&mut bytes)?;
+
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Compute the S256 code challenge for a code verifier
+pub fn code_challenge_s256(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_challenge_rfc_vector() {
+        // Test vector from RFC 7636, Appendix B
+        assert_eq!(
+            code_challenge_s256("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+}
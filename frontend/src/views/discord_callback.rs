@@ -1,10 +1,15 @@
 use crate::config::Config;
+use crate::services::DiscordService;
 use dioxus::prelude::*;
 use dioxus_router::hooks::use_navigator;
 
 /// Discord OAuth callback handler component
 #[component]
-pub fn DiscordCallback(code: Option<String>, error: Option<String>) -> Element {
+pub fn DiscordCallback(
+    code: Option<String>,
+    error: Option<String>,
+    state: Option<String>,
+) -> Element {
     let nav = use_navigator();
 
     // Log that the component is being rendered
@@ -15,6 +20,7 @@ pub fn DiscordCallback(code: Option<String>, error: Option<String>) -> Element {
         let nav = nav.clone();
         let code_clone = code.clone();
         let error_clone = error.clone();
+        let state_clone = state.clone();
         spawn(async move {
             web_sys::console::log_1(&"🔍 Starting OAuth callback processing".into());
             web_sys::console::log_1(
@@ -32,6 +38,19 @@ pub fn DiscordCallback(code: Option<String>, error: Option<String>) -> Element {
                 return;
             }
 
+            // The state must match the one this tab stored before leaving for Discord
+            let stored_flow = DiscordService::take_oauth_flow();
+            let (state, code_verifier) = match (state_clone, stored_flow) {
+                (Some(state), Some((expected, code_verifier))) if state == expected => {
+                    (state, code_verifier)
+                }
+                _ => {
+                    web_sys::console::log_1(&"❌ OAuth state missing or mismatched".into());
+                    nav.push("/?error=invalid_state".to_string());
+                    return;
+                }
+            };
+
             // Check for authorization code from props
             if let Some(code) = code_clone {
                 web_sys::console::log_1(
@@ -44,12 +63,18 @@ pub fn DiscordCallback(code: Option<String>, error: Option<String>) -> Element {
                 );
 
                 // Send code to backend via API request
-                match send_code_to_backend(code).await {
-                    Ok(user_id) => {
+                match send_code_to_backend(code, state, code_verifier).await {
+                    Ok((user_id, session_token)) => {
                         web_sys::console::log_1(
                             &format!("Successfully authenticated user: {}", user_id).into(),
                         );
-                        nav.push(format!("/?auth=success&user_id={}", user_id));
+                        let _ = DiscordService::store_auth_state(
+                            &user_id,
+                            &session_token,
+                            "Discord User",
+                            None,
+                        );
+                        nav.push("/?auth=success".to_string());
                     }
                     Err(e) => {
                         web_sys::console::log_1(&format!("Authentication failed: {}", e).into());
@@ -84,18 +109,34 @@ pub fn DiscordCallback(code: Option<String>, error: Option<String>) -> Element {
     }
 }
 
-async fn send_code_to_backend(code: String) -> Result<String, String> {
+async fn send_code_to_backend(
+    code: String,
+    state: String,
+    code_verifier: String,
+) -> Result<(String, String), String> {
     use wasm_bindgen::JsCast;
     use wasm_bindgen_futures::JsFuture;
-    use web_sys::{Request, RequestInit, Response};
+    use web_sys::{Headers, Request, RequestInit, Response};
 
-    // Send authorization code to backend exchange endpoint
-    let url = Config::backend_discord_exchange_url(&code);
+    // Send authorization code to backend exchange endpoint; the verifier stays out of the URL
+    let body = serde_json::json!({
+        "code": code,
+        "state": state,
+        "code_verifier": code_verifier
+    })
+    .to_string();
 
     let opts = RequestInit::new();
-    opts.set_method("GET");
+    opts.set_method("POST");
+    opts.set_body(&wasm_bindgen::JsValue::from_str(&body));
 
-    let request = Request::new_with_str_and_init(&url, &opts)
+    let headers = Headers::new().map_err(|e| format!("Failed to create headers: {:?}", e))?;
+    headers
+        .set("Content-Type", "application/json")
+        .map_err(|e| format!("Failed to set headers: {:?}", e))?;
+    opts.set_headers(&headers);
+
+    let request = Request::new_with_str_and_init(&Config::backend_discord_exchange_url(), &opts)
         .map_err(|e| format!("Failed to create request: {:?}", e))?;
 
     let window = web_sys::window().ok_or("No window object")?;
@@ -142,5 +183,10 @@ async fn send_code_to_backend(code: String) -> Result<String, String> {
         .and_then(|v| v.as_str())
         .ok_or("No user_id in backend response")?;
 
-    Ok(user_id.to_string())
+    let session_token = json
+        .get("session_token")
+        .and_then(|v| v.as_str())
+        .ok_or("No session_token in backend response")?;
+
+    Ok((user_id.to_string(), session_token.to_string()))
 }
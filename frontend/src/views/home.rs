@@ -23,7 +23,7 @@ pub fn Home() -> Element {
 
     // Check for authentication state on page load
     use_effect(move || {
-        // Check for auth success in URL query parameters (auth=success&user_id=...)
+        // Check for auth success in URL query parameters (auth=success[&session_token=...])
         if let Some(window) = web_sys::window() {
             if let Ok(href) = window.location().href() {
                 if let Ok(url) = web_sys::Url::new(&href) {
@@ -32,26 +32,22 @@ pub fn Home() -> Element {
                     // Check for successful auth redirect
                     if let Some(auth) = search_params.get("auth") {
                         if auth == "success" {
-                            if let Some(user_id) = search_params.get("user_id") {
-                                auth_status.set("success".to_string());
-                                is_logged_in.set(true);
-                                show_leaderboard.set(true);
-
-                                // Create user info
-                                let username = "Discord User".to_string();
-                                let avatar_url = None;
-
-                                user_info.set(Some(UserInfo {
-                                    id: user_id.clone(),
-                                    username: username.clone(),
-                                    avatar_url: avatar_url.clone(),
-                                }));
-
-                                // Store in localStorage
-                                let _ = DiscordService::store_auth_state(
-                                    &user_id, &username, avatar_url,
-                                );
-                                return;
+                            auth_status.set("success".to_string());
+
+                            // The backend redirect flow passes the session token in the URL;
+                            // the frontend exchange flow has already stored it.
+                            if let Some(session_token) = search_params.get("session_token") {
+                                if let Some(user_id) =
+                                    DiscordService::user_id_from_session_token(&session_token)
+                                {
+                                    let _ = DiscordService::store_auth_state(
+                                        &user_id,
+                                        &session_token,
+                                        "Discord User",
+                                        None,
+                                    );
+                                }
+                                let _ = DiscordService::clear_url_params();
                             }
                         }
                     }
@@ -65,7 +61,7 @@ pub fn Home() -> Element {
             }
         }
 
-        // No OAuth callback - check localStorage
+        // Load the stored session (set by either OAuth flow above or a previous visit)
         if let Some((user_id, username, avatar_url)) = DiscordService::get_stored_auth_state() {
             is_logged_in.set(true);
             user_info.set(Some(UserInfo {
@@ -78,7 +74,12 @@ pub fn Home() -> Element {
     });
 
     let handle_discord_login = move |_| {
-        let _ = DiscordService::start_oauth_flow();
+        spawn(async move {
+            if let Err(e) = DiscordService::start_oauth_flow().await {
+                web_sys::console::log_1(&format!("Failed to start OAuth flow: {:?}", e).into());
+                auth_status.set("error".to_string());
+            }
+        });
     };
 
     let handle_logout = move |_| {
@@ -87,11 +88,18 @@ pub fn Home() -> Element {
         auth_status.set(String::new());
         show_leaderboard.set(false);
 
-        // Clear stored authentication state
-        let _ = DiscordService::clear_stored_auth_state();
+        spawn(async move {
+            // End the session server-side before forgetting the token locally
+            if let Err(e) = DiscordService::logout().await {
+                web_sys::console::log_1(&format!("Backend logout failed: {:?}", e).into());
+            }
+
+            // Clear stored authentication state
+            let _ = DiscordService::clear_stored_auth_state();
 
-        // Clear URL parameters
-        let _ = DiscordService::clear_url_params();
+            // Clear URL parameters
+            let _ = DiscordService::clear_url_params();
+        });
     };
 
     rsx! {
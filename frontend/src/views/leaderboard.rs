@@ -2,8 +2,9 @@ use crate::components::Leaderboard;
 use dioxus::prelude::*;
 use dioxus_router::hooks::use_navigator;
 
+/// Full-page leaderboard of the org and game type given in the query string
 #[component]
-pub fn LeaderboardPage() -> Element {
+pub fn LeaderboardPage(org: Option<String>, game: Option<String>) -> Element {
     let nav = use_navigator();
 
     rsx! {
@@ -17,7 +18,7 @@ pub fn LeaderboardPage() -> Element {
                     "← Back to Home"
                 }
             }
-            Leaderboard {}
+            Leaderboard { org_id: org, game_type: game }
         }
     }
 }
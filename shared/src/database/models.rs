@@ -5,7 +5,12 @@ use uuid::Uuid;
 #[cfg(feature = "database")]
 use sqlx::FromRow;
 
-use crate::models::{DiscordOrg, Member, MemberStatus, User};
+use crate::models::{
+    DiscordOrg, JoinRequest, JoinRequestStatus, LeaderboardEntry, Match, MatchConfirmation,
+    MatchParticipant, MatchStatus, Member, MemberBan, MemberStatus, MemberStatusChange, OrgListing,
+    OrgRole, OwnershipTransfer, OwnershipTransferStatus, PlayerRating, RatingChange, Season, User,
+};
+use crate::rating::Rating;
 
 // Database model for users table
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,10 +62,92 @@ pub struct DbDiscordOrg {
     pub name: String,
     pub avatar_url: Option<String>,
     pub description: Option<String>,
+    pub guild_id: Option<String>, // Discord guild snowflake
+    pub requires_approval: bool,
+    pub listed: bool,      // Shown in the public directory
+    pub tags: Vec<String>, // Lowercase directory tags
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl DbDiscordOrg {
+    /// Build the API model once the owner has been loaded
+    pub fn into_org(self, owner: User) -> DiscordOrg {
+        DiscordOrg {
+            id: self.id,
+            owner,
+            name: self.name,
+            avatar_url: self.avatar_url,
+            description: self.description,
+            guild_id: self.guild_id,
+            requires_approval: self.requires_approval,
+            listed: self.listed,
+            tags: self.tags,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+// Database model for a directory listing: an org joined with its owner and member count
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbOrgListing {
+    // DiscordOrg fields
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub name: String,
+    pub avatar_url: Option<String>,
+    pub description: Option<String>,
+    pub guild_id: Option<String>,
+    pub requires_approval: bool,
+    pub listed: bool,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    // Owner fields (prefixed with owner_)
+    pub owner_discord_id: String,
+    pub owner_display_name: String,
+    pub owner_avatar_url: Option<String>,
+    pub owner_bio: Option<String>,
+    pub owner_created_at: DateTime<Utc>,
+    pub owner_updated_at: DateTime<Utc>,
+
+    pub member_count: i64,
+}
+
+impl From<DbOrgListing> for OrgListing {
+    fn from(db_listing: DbOrgListing) -> Self {
+        let owner = User {
+            id: db_listing.owner_id,
+            discord_id: db_listing.owner_discord_id,
+            display_name: db_listing.owner_display_name,
+            avatar_url: db_listing.owner_avatar_url,
+            bio: db_listing.owner_bio,
+            created_at: db_listing.owner_created_at,
+            updated_at: db_listing.owner_updated_at,
+        };
+
+        OrgListing {
+            org: DiscordOrg {
+                id: db_listing.id,
+                owner,
+                name: db_listing.name,
+                avatar_url: db_listing.avatar_url,
+                description: db_listing.description,
+                guild_id: db_listing.guild_id,
+                requires_approval: db_listing.requires_approval,
+                listed: db_listing.listed,
+                tags: db_listing.tags,
+                created_at: db_listing.created_at,
+                updated_at: db_listing.updated_at,
+            },
+            member_count: db_listing.member_count,
+        }
+    }
+}
+
 // Database model for members table
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "database", derive(FromRow))]
@@ -69,6 +156,7 @@ pub struct DbMember {
     pub user_id: Uuid,        // Foreign key to users table
     pub discord_org_id: Uuid, // Foreign key to discord_orgs table
     pub status: String,       // MemberStatus as string for database storage
+    pub role: String,         // OrgRole as string for database storage
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -96,6 +184,31 @@ impl TryFrom<String> for MemberStatus {
     }
 }
 
+impl From<OrgRole> for String {
+    fn from(role: OrgRole) -> Self {
+        match role {
+            OrgRole::Owner => "owner".to_string(),
+            OrgRole::Admin => "admin".to_string(),
+            OrgRole::Moderator => "moderator".to_string(),
+            OrgRole::Member => "member".to_string(),
+        }
+    }
+}
+
+impl TryFrom<String> for OrgRole {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "owner" => Ok(OrgRole::Owner),
+            "admin" => Ok(OrgRole::Admin),
+            "moderator" => Ok(OrgRole::Moderator),
+            "member" => Ok(OrgRole::Member),
+            _ => Err(format!("Invalid org role: {}", value)),
+        }
+    }
+}
+
 // Join query result for getting a member with user and org data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "database", derive(FromRow))]
@@ -103,6 +216,7 @@ pub struct DbMemberWithRelations {
     // Member fields
     pub id: Uuid,
     pub status: String,
+    pub role: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 
@@ -121,6 +235,10 @@ pub struct DbMemberWithRelations {
     pub org_name: String,
     pub org_avatar_url: Option<String>,
     pub org_description: Option<String>,
+    pub org_guild_id: Option<String>,
+    pub org_requires_approval: bool,
+    pub org_listed: bool,
+    pub org_tags: Vec<String>,
     pub org_created_at: DateTime<Utc>,
     pub org_updated_at: DateTime<Utc>,
 
@@ -163,17 +281,23 @@ impl TryFrom<DbMemberWithRelations> for Member {
             name: db_member.org_name,
             avatar_url: db_member.org_avatar_url,
             description: db_member.org_description,
+            guild_id: db_member.org_guild_id,
+            requires_approval: db_member.org_requires_approval,
+            listed: db_member.org_listed,
+            tags: db_member.org_tags,
             created_at: db_member.org_created_at,
             updated_at: db_member.org_updated_at,
         };
 
         let status = MemberStatus::try_from(db_member.status)?;
+        let role = OrgRole::try_from(db_member.role)?;
 
         Ok(Member {
             id: db_member.id,
             user,
             discord_org,
             status,
+            role,
             created_at: db_member.created_at,
             updated_at: db_member.updated_at,
         })
@@ -195,6 +319,10 @@ pub struct CreateDiscordOrg {
     pub name: String,
     pub avatar_url: Option<String>,
     pub description: Option<String>,
+    pub guild_id: Option<String>,
+    pub requires_approval: bool,
+    pub listed: bool,
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -202,6 +330,7 @@ pub struct CreateMember {
     pub user_id: Uuid,
     pub discord_org_id: Uuid,
     pub status: MemberStatus,
+    pub role: OrgRole,
 }
 
 // Update models for modifying existing records
@@ -217,11 +346,15 @@ pub struct UpdateDiscordOrg {
     pub name: Option<String>,
     pub avatar_url: Option<String>,
     pub description: Option<String>,
+    pub requires_approval: Option<bool>,
+    pub listed: Option<bool>,
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateMember {
     pub status: Option<MemberStatus>,
+    pub role: Option<OrgRole>,
 }
 
 // Database model for discord_tokens table
@@ -235,6 +368,9 @@ pub struct DbDiscordToken {
     pub token_type: String,
     pub scope: Option<String>,
     pub expires_at: Option<DateTime<Utc>>,
+    pub invalidated_at: Option<DateTime<Utc>>,
+    pub key_id: Option<String>, // Encryption key that sealed the tokens
+    pub access_token_hash: Option<String>, // Keyed hash for lookups
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -259,3 +395,628 @@ pub struct UpdateDiscordToken {
     pub scope: Option<String>,
     pub expires_at: Option<DateTime<Utc>>,
 }
+
+// Database model for oauth_states table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbOAuthState {
+    pub state: String,
+    pub code_challenge: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+// Database model for sessions table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbSession {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub device_label: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+// Create session request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSession {
+    pub user_id: Uuid,
+    pub device_label: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+// Database model for api_keys table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbApiKey {
+    pub id: Uuid,
+    pub discord_org_id: Uuid,     // Foreign key to discord_orgs table
+    pub created_by: Option<Uuid>, // Foreign key to users table
+    pub name: String,
+    pub key_prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<String>, // ApiKeyScope values as strings for database storage
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Input model for creating new api keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateApiKey {
+    pub discord_org_id: Uuid,
+    pub created_by: Option<Uuid>,
+    pub name: String,
+    pub key_prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+// Database model for org_invites table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbOrgInvite {
+    pub id: Uuid,
+    pub discord_org_id: Uuid,     // Foreign key to discord_orgs table
+    pub created_by: Option<Uuid>, // Foreign key to users table
+    pub code: String,
+    pub initial_status: String, // MemberStatus as string for database storage
+    pub max_uses: Option<i32>,
+    pub uses: i32,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Input model for creating new org invites
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateOrgInvite {
+    pub discord_org_id: Uuid,
+    pub created_by: Option<Uuid>,
+    pub code: String,
+    pub initial_status: MemberStatus,
+    pub max_uses: Option<i32>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+// Database model for invite_redemptions joined with the redeeming user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbInviteRedemptionWithUser {
+    pub id: Uuid,
+    pub invite_id: Uuid,
+    pub member_id: Option<Uuid>, // Foreign key to members table
+    pub redeemed_at: DateTime<Utc>,
+
+    // User fields (prefixed with user_)
+    pub user_id: Uuid,
+    pub user_discord_id: String,
+    pub user_display_name: String,
+    pub user_avatar_url: Option<String>,
+    pub user_bio: Option<String>,
+    pub user_created_at: DateTime<Utc>,
+    pub user_updated_at: DateTime<Utc>,
+}
+
+// Database model for member_bans table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbMemberBan {
+    pub id: Uuid,
+    pub discord_org_id: Uuid,    // Foreign key to discord_orgs table
+    pub user_id: Uuid,           // Foreign key to users table
+    pub banned_by: Option<Uuid>, // Foreign key to users table
+    pub reason: Option<String>,
+    pub banned_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub lifted_at: Option<DateTime<Utc>>,
+    pub lifted_by: Option<Uuid>, // Foreign key to users table
+}
+
+impl From<DbMemberBan> for MemberBan {
+    fn from(db_ban: DbMemberBan) -> Self {
+        MemberBan {
+            id: db_ban.id,
+            discord_org_id: db_ban.discord_org_id,
+            user_id: db_ban.user_id,
+            banned_by: db_ban.banned_by,
+            reason: db_ban.reason,
+            banned_at: db_ban.banned_at,
+            expires_at: db_ban.expires_at,
+            lifted_at: db_ban.lifted_at,
+            lifted_by: db_ban.lifted_by,
+        }
+    }
+}
+
+// Input model for banning a member
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateMemberBan {
+    pub discord_org_id: Uuid,
+    pub user_id: Uuid,
+    pub banned_by: Option<Uuid>,
+    pub reason: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+// Database model for member_status_history table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbMemberStatusChange {
+    pub id: Uuid,
+    pub member_id: Uuid,             // Foreign key to members table
+    pub from_status: Option<String>, // MemberStatus as string for database storage
+    pub to_status: String,
+    pub changed_by: Option<Uuid>, // Foreign key to users table
+    pub reason: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl TryFrom<DbMemberStatusChange> for MemberStatusChange {
+    type Error = String;
+
+    fn try_from(db_change: DbMemberStatusChange) -> Result<Self, Self::Error> {
+        Ok(MemberStatusChange {
+            id: db_change.id,
+            member_id: db_change.member_id,
+            from_status: db_change
+                .from_status
+                .map(MemberStatus::try_from)
+                .transpose()?,
+            to_status: MemberStatus::try_from(db_change.to_status)?,
+            changed_by: db_change.changed_by,
+            reason: db_change.reason,
+            changed_at: db_change.changed_at,
+        })
+    }
+}
+
+// Database model for join_requests table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbJoinRequest {
+    pub id: Uuid,
+    pub discord_org_id: Uuid, // Foreign key to discord_orgs table
+    pub user_id: Uuid,        // Foreign key to users table
+    pub message: Option<String>,
+    pub status: String, // JoinRequestStatus as string for database storage
+    pub decided_by: Option<Uuid>, // Foreign key to users table
+    pub decision_reason: Option<String>,
+    pub member_id: Option<Uuid>, // Foreign key to members table
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+impl From<JoinRequestStatus> for String {
+    fn from(status: JoinRequestStatus) -> Self {
+        match status {
+            JoinRequestStatus::Pending => "pending".to_string(),
+            JoinRequestStatus::Approved => "approved".to_string(),
+            JoinRequestStatus::Rejected => "rejected".to_string(),
+            JoinRequestStatus::Withdrawn => "withdrawn".to_string(),
+        }
+    }
+}
+
+impl TryFrom<String> for JoinRequestStatus {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "pending" => Ok(JoinRequestStatus::Pending),
+            "approved" => Ok(JoinRequestStatus::Approved),
+            "rejected" => Ok(JoinRequestStatus::Rejected),
+            "withdrawn" => Ok(JoinRequestStatus::Withdrawn),
+            _ => Err(format!("Invalid join request status: {}", value)),
+        }
+    }
+}
+
+impl TryFrom<DbJoinRequest> for JoinRequest {
+    type Error = String;
+
+    fn try_from(db_request: DbJoinRequest) -> Result<Self, Self::Error> {
+        Ok(JoinRequest {
+            id: db_request.id,
+            discord_org_id: db_request.discord_org_id,
+            user_id: db_request.user_id,
+            message: db_request.message,
+            status: JoinRequestStatus::try_from(db_request.status)?,
+            decided_by: db_request.decided_by,
+            decision_reason: db_request.decision_reason,
+            member_id: db_request.member_id,
+            created_at: db_request.created_at,
+            decided_at: db_request.decided_at,
+        })
+    }
+}
+
+// Input model for requesting to join an org
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateJoinRequest {
+    pub discord_org_id: Uuid,
+    pub user_id: Uuid,
+    pub message: Option<String>,
+}
+
+// Database model for ownership_transfers table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbOwnershipTransfer {
+    pub id: Uuid,
+    pub discord_org_id: Uuid,       // Foreign key to discord_orgs table
+    pub from_user_id: Option<Uuid>, // Foreign key to users table
+    pub to_user_id: Uuid,           // Foreign key to users table
+    pub initiated_by: Option<Uuid>, // Foreign key to users table
+    pub status: String,             // OwnershipTransferStatus as string for database storage
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<String> for OwnershipTransferStatus {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "pending" => Ok(OwnershipTransferStatus::Pending),
+            "accepted" => Ok(OwnershipTransferStatus::Accepted),
+            "declined" => Ok(OwnershipTransferStatus::Declined),
+            "cancelled" => Ok(OwnershipTransferStatus::Cancelled),
+            _ => Err(format!("Invalid ownership transfer status: {}", value)),
+        }
+    }
+}
+
+impl TryFrom<DbOwnershipTransfer> for OwnershipTransfer {
+    type Error = String;
+
+    fn try_from(db_transfer: DbOwnershipTransfer) -> Result<Self, Self::Error> {
+        Ok(OwnershipTransfer {
+            id: db_transfer.id,
+            discord_org_id: db_transfer.discord_org_id,
+            from_user_id: db_transfer.from_user_id,
+            to_user_id: db_transfer.to_user_id,
+            initiated_by: db_transfer.initiated_by,
+            status: OwnershipTransferStatus::try_from(db_transfer.status)?,
+            expires_at: db_transfer.expires_at,
+            created_at: db_transfer.created_at,
+            resolved_at: db_transfer.resolved_at,
+        })
+    }
+}
+
+// Input model for nominating a new org owner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateOwnershipTransfer {
+    pub discord_org_id: Uuid,
+    pub from_user_id: Uuid,
+    pub to_user_id: Uuid,
+    pub initiated_by: Option<Uuid>,
+    pub expires_at: DateTime<Utc>,
+}
+
+// Database model for matches table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbMatch {
+    pub id: Uuid,
+    pub discord_org_id: Uuid, // Foreign key to discord_orgs table
+    pub game_type: String,
+    pub reported_by: Option<Uuid>, // Foreign key to users table
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub status: String, // MatchStatus as string for database storage
+    pub confirm_deadline: DateTime<Utc>,
+    pub finalized_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<Uuid>, // Foreign key to users table
+    pub resolution_note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Database model for match_participants table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbMatchParticipant {
+    pub id: Uuid,
+    pub match_id: Uuid, // Foreign key to matches table
+    pub user_id: Uuid,  // Foreign key to users table
+    pub placement: i32,
+    pub score: Option<f64>,
+    pub confirmation: String, // MatchConfirmation as string for database storage
+    pub dispute_reason: Option<String>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<MatchStatus> for String {
+    fn from(status: MatchStatus) -> Self {
+        match status {
+            MatchStatus::Pending => "pending".to_string(),
+            MatchStatus::Disputed => "disputed".to_string(),
+            MatchStatus::Finalized => "finalized".to_string(),
+            MatchStatus::Voided => "voided".to_string(),
+        }
+    }
+}
+
+impl TryFrom<String> for MatchStatus {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "pending" => Ok(MatchStatus::Pending),
+            "disputed" => Ok(MatchStatus::Disputed),
+            "finalized" => Ok(MatchStatus::Finalized),
+            "voided" => Ok(MatchStatus::Voided),
+            _ => Err(format!("Invalid match status: {}", value)),
+        }
+    }
+}
+
+impl From<MatchConfirmation> for String {
+    fn from(confirmation: MatchConfirmation) -> Self {
+        match confirmation {
+            MatchConfirmation::Pending => "pending".to_string(),
+            MatchConfirmation::Confirmed => "confirmed".to_string(),
+            MatchConfirmation::Disputed => "disputed".to_string(),
+        }
+    }
+}
+
+impl TryFrom<String> for MatchConfirmation {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "pending" => Ok(MatchConfirmation::Pending),
+            "confirmed" => Ok(MatchConfirmation::Confirmed),
+            "disputed" => Ok(MatchConfirmation::Disputed),
+            _ => Err(format!("Invalid match confirmation: {}", value)),
+        }
+    }
+}
+
+impl TryFrom<DbMatchParticipant> for MatchParticipant {
+    type Error = String;
+
+    fn try_from(db_participant: DbMatchParticipant) -> Result<Self, Self::Error> {
+        Ok(MatchParticipant {
+            user_id: db_participant.user_id,
+            placement: db_participant.placement,
+            score: db_participant.score,
+            confirmation: MatchConfirmation::try_from(db_participant.confirmation)?,
+            dispute_reason: db_participant.dispute_reason,
+        })
+    }
+}
+
+impl TryFrom<(DbMatch, Vec<DbMatchParticipant>)> for Match {
+    type Error = String;
+
+    fn try_from(
+        (db_match, participants): (DbMatch, Vec<DbMatchParticipant>),
+    ) -> Result<Self, Self::Error> {
+        Ok(Match {
+            id: db_match.id,
+            discord_org_id: db_match.discord_org_id,
+            game_type: db_match.game_type,
+            reported_by: db_match.reported_by,
+            started_at: db_match.started_at,
+            ended_at: db_match.ended_at,
+            status: MatchStatus::try_from(db_match.status)?,
+            confirm_deadline: db_match.confirm_deadline,
+            finalized_at: db_match.finalized_at,
+            resolved_by: db_match.resolved_by,
+            resolution_note: db_match.resolution_note,
+            participants: participants
+                .into_iter()
+                .map(MatchParticipant::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+            created_at: db_match.created_at,
+            updated_at: db_match.updated_at,
+        })
+    }
+}
+
+// Input model for recording a match with its results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateMatch {
+    pub discord_org_id: Uuid,
+    pub game_type: String,
+    pub reported_by: Option<Uuid>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub confirm_deadline: DateTime<Utc>,
+    pub participants: Vec<CreateMatchParticipant>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateMatchParticipant {
+    pub user_id: Uuid,
+    pub placement: i32,
+    pub score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateMatch {
+    pub game_type: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub participants: Option<Vec<CreateMatchParticipant>>, // Replaces every result when set
+}
+
+// Database model for ratings table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbRating {
+    pub id: Uuid,
+    pub discord_org_id: Uuid, // Foreign key to discord_orgs table
+    pub user_id: Uuid,        // Foreign key to users table
+    pub game_type: String,
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+    pub games_played: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<&DbRating> for Rating {
+    fn from(db_rating: &DbRating) -> Self {
+        Rating {
+            rating: db_rating.rating,
+            deviation: db_rating.deviation,
+            volatility: db_rating.volatility,
+        }
+    }
+}
+
+impl From<DbRating> for PlayerRating {
+    fn from(db_rating: DbRating) -> Self {
+        PlayerRating {
+            user_id: db_rating.user_id,
+            discord_org_id: db_rating.discord_org_id,
+            game_type: db_rating.game_type,
+            rating: db_rating.rating,
+            deviation: db_rating.deviation,
+            volatility: db_rating.volatility,
+            games_played: db_rating.games_played,
+            updated_at: db_rating.updated_at,
+        }
+    }
+}
+
+// Database model for a leaderboard row: a rating joined with its user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbLeaderboardEntry {
+    pub rank: i64,
+
+    // Rating fields
+    pub discord_org_id: Uuid,
+    pub game_type: String,
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+    pub games_played: i32,
+    pub updated_at: DateTime<Utc>,
+
+    // User fields (prefixed with user_)
+    pub user_id: Uuid,
+    pub user_discord_id: String,
+    pub user_display_name: String,
+    pub user_avatar_url: Option<String>,
+    pub user_bio: Option<String>,
+    pub user_created_at: DateTime<Utc>,
+    pub user_updated_at: DateTime<Utc>,
+}
+
+impl From<DbLeaderboardEntry> for LeaderboardEntry {
+    fn from(db_entry: DbLeaderboardEntry) -> Self {
+        LeaderboardEntry {
+            rank: db_entry.rank,
+            user: User {
+                id: db_entry.user_id,
+                discord_id: db_entry.user_discord_id,
+                display_name: db_entry.user_display_name,
+                avatar_url: db_entry.user_avatar_url,
+                bio: db_entry.user_bio,
+                created_at: db_entry.user_created_at,
+                updated_at: db_entry.user_updated_at,
+            },
+            rating: PlayerRating {
+                user_id: db_entry.user_id,
+                discord_org_id: db_entry.discord_org_id,
+                game_type: db_entry.game_type,
+                rating: db_entry.rating,
+                deviation: db_entry.deviation,
+                volatility: db_entry.volatility,
+                games_played: db_entry.games_played,
+                updated_at: db_entry.updated_at,
+            },
+        }
+    }
+}
+
+// Database model for rating_history table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbRatingHistory {
+    pub id: Uuid,
+    pub match_id: Uuid,       // Foreign key to matches table
+    pub discord_org_id: Uuid, // Foreign key to discord_orgs table
+    pub user_id: Uuid,        // Foreign key to users table
+    pub game_type: String,
+    pub rating_before: f64,
+    pub deviation_before: f64,
+    pub volatility_before: f64,
+    pub rating_after: f64,
+    pub deviation_after: f64,
+    pub volatility_after: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DbRatingHistory> for RatingChange {
+    fn from(db_history: DbRatingHistory) -> Self {
+        RatingChange {
+            match_id: db_history.match_id,
+            rating_before: db_history.rating_before,
+            deviation_before: db_history.deviation_before,
+            rating_after: db_history.rating_after,
+            deviation_after: db_history.deviation_after,
+            created_at: db_history.created_at,
+        }
+    }
+}
+
+// Database model for seasons table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "database", derive(FromRow))]
+pub struct DbSeason {
+    pub id: Uuid,
+    pub discord_org_id: Uuid, // Foreign key to discord_orgs table
+    pub name: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub soft_reset: f64,
+    pub created_by: Option<Uuid>, // Foreign key to users table
+    pub archived_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<DbSeason> for Season {
+    fn from(db_season: DbSeason) -> Self {
+        Season {
+            id: db_season.id,
+            discord_org_id: db_season.discord_org_id,
+            name: db_season.name,
+            starts_at: db_season.starts_at,
+            ends_at: db_season.ends_at,
+            soft_reset: db_season.soft_reset,
+            archived_at: db_season.archived_at,
+            created_at: db_season.created_at,
+        }
+    }
+}
+
+// Input model for creating a season
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSeason {
+    pub discord_org_id: Uuid,
+    pub name: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub soft_reset: f64,
+    pub created_by: Option<Uuid>,
+}
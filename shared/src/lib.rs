@@ -1,4 +1,5 @@
 pub mod models;
+pub mod rating;
 
 #[cfg(feature = "database")]
 pub mod database;
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// What an org API key is allowed to do
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub enum ApiKeyScope {
+    #[serde(rename = "members:read")]
+    MembersRead,
+    #[serde(rename = "matches:write")]
+    MatchesWrite,
+    #[serde(rename = "leaderboard:read")]
+    LeaderboardRead,
+}
+
+impl ApiKeyScope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiKeyScope::MembersRead => "members:read",
+            ApiKeyScope::MatchesWrite => "matches:write",
+            ApiKeyScope::LeaderboardRead => "leaderboard:read",
+        }
+    }
+}
+
+impl TryFrom<&str> for ApiKeyScope {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "members:read" => Ok(ApiKeyScope::MembersRead),
+            "matches:write" => Ok(ApiKeyScope::MatchesWrite),
+            "leaderboard:read" => Ok(ApiKeyScope::LeaderboardRead),
+            _ => Err(format!("Invalid API key scope: {}", value)),
+        }
+    }
+}
@@ -11,6 +11,49 @@ pub struct DiscordOrg {
     pub name: String,
     pub avatar_url: Option<String>,
     pub description: Option<String>,
+    pub guild_id: Option<String>, // Discord guild the org was imported from
+    pub requires_approval: bool,  // Joining creates a join request for admins to review
+    pub listed: bool,             // Shown in the public directory
+    pub tags: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+/// An org as shown in the public directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrgListing {
+    pub org: DiscordOrg,
+    pub member_count: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub enum OwnershipTransferStatus {
+    Pending,
+    Accepted,
+    Declined,
+    Cancelled,
+}
+
+/// A nomination to hand an org to another member; the nominee has to accept it
+/// before `expires_at`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnershipTransfer {
+    pub id: Uuid,
+    pub discord_org_id: Uuid,
+    pub from_user_id: Option<Uuid>,
+    pub to_user_id: Uuid,
+    pub initiated_by: Option<Uuid>,
+    pub status: OwnershipTransferStatus,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// A Discord guild the user can import as an org (they own it or hold Manage Server)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscordGuild {
+    pub id: String,
+    pub name: String,
+    pub icon_url: Option<String>,
+    pub org_id: Option<Uuid>, // Set once the guild has been imported
+}
@@ -0,0 +1,98 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::user::User;
+
+/// Where a reported match is in the confirmation process; only `Finalized` matches
+/// count towards stats
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub enum MatchStatus {
+    Pending,  // Waiting for participants to confirm until `confirm_deadline`
+    Disputed, // A participant disputed the result; an org admin has to resolve it
+    Finalized,
+    Voided, // Thrown out by an org admin
+}
+
+/// A participant's answer to a reported result
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub enum MatchConfirmation {
+    Pending,
+    Confirmed,
+    Disputed,
+}
+
+/// A finished game played within an org
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Match {
+    pub id: Uuid,
+    pub discord_org_id: Uuid,
+    pub game_type: String,
+    pub reported_by: Option<Uuid>, // None when recorded with an API key
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub status: MatchStatus,
+    pub confirm_deadline: DateTime<Utc>,
+    pub finalized_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<Uuid>, // Org admin who resolved a dispute
+    pub resolution_note: Option<String>,
+    pub participants: Vec<MatchParticipant>, // Ordered by placement
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One player's result in a match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchParticipant {
+    pub user_id: Uuid,
+    pub placement: i32, // Starts at 1; tied players share a placement
+    pub score: Option<f64>,
+    pub confirmation: MatchConfirmation,
+    pub dispute_reason: Option<String>,
+}
+
+/// A player's current rating in one org and game type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerRating {
+    pub user_id: Uuid,
+    pub discord_org_id: Uuid,
+    pub game_type: String,
+    pub rating: f64,
+    pub deviation: f64, // Rating deviation; lower means more certain
+    pub volatility: f64,
+    pub games_played: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A row of an org's leaderboard for one game type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub rank: i64,
+    pub user: User,
+    pub rating: PlayerRating,
+}
+
+/// How one finalized match changed a player's rating
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingChange {
+    pub match_id: Uuid,
+    pub rating_before: f64,
+    pub deviation_before: f64,
+    pub rating_after: f64,
+    pub deviation_after: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A dated competitive season of an org; when it ends its leaderboards are archived
+/// and ratings are pulled back towards the default
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Season {
+    pub id: Uuid,
+    pub discord_org_id: Uuid,
+    pub name: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub soft_reset: f64, // 0 keeps ratings, 1 resets them fully
+    pub archived_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
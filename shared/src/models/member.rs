@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::models::discord::DiscordOrg;
+use crate::models::role::OrgRole;
 use crate::models::user::User;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Hash, PartialEq, Eq)]
@@ -12,12 +13,90 @@ pub enum MemberStatus {
     Banned,
 }
 
+impl MemberStatus {
+    /// Whether a member may move from this status to `next`. Bans are only lifted through
+    /// the unban endpoint (which also closes the ban), so `Banned` can't move anywhere here.
+    pub fn can_transition_to(&self, next: &MemberStatus) -> bool {
+        matches!(
+            (self, next),
+            (MemberStatus::Spectating, MemberStatus::Playing)
+                | (MemberStatus::Playing, MemberStatus::Spectating)
+                | (MemberStatus::Spectating, MemberStatus::Banned)
+                | (MemberStatus::Playing, MemberStatus::Banned)
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Member {
     pub id: Uuid,
     pub user: User,
     pub discord_org: DiscordOrg,
     pub status: MemberStatus,
+    pub role: OrgRole,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+/// A ban from an org; it is active until `lifted_at` is set or `expires_at` passes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberBan {
+    pub id: Uuid,
+    pub discord_org_id: Uuid,
+    pub user_id: Uuid,
+    pub banned_by: Option<Uuid>,
+    pub reason: Option<String>,
+    pub banned_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub lifted_at: Option<DateTime<Utc>>,
+    pub lifted_by: Option<Uuid>,
+}
+
+/// One entry in a member's status history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberStatusChange {
+    pub id: Uuid,
+    pub member_id: Uuid,
+    pub from_status: Option<MemberStatus>, // None when the membership was created
+    pub to_status: MemberStatus,
+    pub changed_by: Option<Uuid>,
+    pub reason: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub enum JoinRequestStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Withdrawn,
+}
+
+/// A user's request to join an org that requires approval
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinRequest {
+    pub id: Uuid,
+    pub discord_org_id: Uuid,
+    pub user_id: Uuid,
+    pub message: Option<String>,
+    pub status: JoinRequestStatus,
+    pub decided_by: Option<Uuid>,
+    pub decision_reason: Option<String>,
+    pub member_id: Option<Uuid>, // Set once the request is approved
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_transitions() {
+        assert!(MemberStatus::Spectating.can_transition_to(&MemberStatus::Playing));
+        assert!(MemberStatus::Playing.can_transition_to(&MemberStatus::Banned));
+        assert!(!MemberStatus::Banned.can_transition_to(&MemberStatus::Spectating));
+        assert!(!MemberStatus::Banned.can_transition_to(&MemberStatus::Playing));
+        assert!(!MemberStatus::Playing.can_transition_to(&MemberStatus::Playing));
+    }
+}
@@ -1,7 +1,13 @@
+mod api_key;
 mod discord;
+mod game;
 mod member;
+mod role;
 mod user;
 
+pub use api_key::*;
 pub use discord::*;
+pub use game::*;
 pub use member::*;
+pub use role::*;
 pub use user::*;
@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use std::ops::BitOr;
+
+/// A member's role within a single org, from most to least privileged
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub enum OrgRole {
+    Owner,
+    Admin,
+    Moderator,
+    Member,
+}
+
+impl OrgRole {
+    /// Permissions granted by this role
+    pub fn permissions(self) -> Permissions {
+        match self {
+            OrgRole::Owner => Permissions::all(),
+            OrgRole::Admin => Permissions::all()
+                .without(Permissions::DELETE_ORG)
+                .without(Permissions::TRANSFER_OWNERSHIP),
+            OrgRole::Moderator => {
+                Permissions::VIEW_MEMBERS | Permissions::MANAGE_MEMBERS | Permissions::BAN_MEMBERS
+            }
+            OrgRole::Member => Permissions::VIEW_MEMBERS,
+        }
+    }
+
+    /// Higher ranks may manage members of strictly lower ranks
+    pub fn rank(self) -> u8 {
+        match self {
+            OrgRole::Owner => 3,
+            OrgRole::Admin => 2,
+            OrgRole::Moderator => 1,
+            OrgRole::Member => 0,
+        }
+    }
+
+    /// Whether a member with this role may act on a member with `other`
+    pub fn outranks(self, other: OrgRole) -> bool {
+        self.rank() > other.rank()
+    }
+}
+
+/// Bitset of org-scoped permissions
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Permissions(u32);
+
+impl Permissions {
+    pub const VIEW_MEMBERS: Permissions = Permissions(1 << 0);
+    pub const MANAGE_MEMBERS: Permissions = Permissions(1 << 1); // Change member status
+    pub const BAN_MEMBERS: Permissions = Permissions(1 << 2);
+    pub const MANAGE_ROLES: Permissions = Permissions(1 << 3);
+    pub const MANAGE_ORG: Permissions = Permissions(1 << 4); // Edit org settings
+    pub const DELETE_ORG: Permissions = Permissions(1 << 5);
+    pub const TRANSFER_OWNERSHIP: Permissions = Permissions(1 << 6);
+    pub const MANAGE_API_KEYS: Permissions = Permissions(1 << 7);
+    pub const MANAGE_INVITES: Permissions = Permissions(1 << 8);
+
+    pub const fn empty() -> Self {
+        Permissions(0)
+    }
+
+    pub const fn all() -> Self {
+        Permissions((1 << 9) - 1)
+    }
+
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Whether every permission in `other` is granted
+    pub const fn contains(self, other: Permissions) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn without(self, other: Permissions) -> Self {
+        Permissions(self.0 & !other.0)
+    }
+}
+
+impl BitOr for Permissions {
+    type Output = Permissions;
+
+    fn bitor(self, rhs: Permissions) -> Permissions {
+        Permissions(self.0 | rhs.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_permissions() {
+        assert!(
+            OrgRole::Owner
+                .permissions()
+                .contains(Permissions::DELETE_ORG)
+        );
+        assert!(
+            !OrgRole::Admin
+                .permissions()
+                .contains(Permissions::DELETE_ORG)
+        );
+        assert!(OrgRole::Admin.permissions().contains(
+            Permissions::BAN_MEMBERS | Permissions::MANAGE_ROLES | Permissions::MANAGE_INVITES
+        ));
+        assert!(
+            OrgRole::Moderator
+                .permissions()
+                .contains(Permissions::BAN_MEMBERS)
+        );
+        assert!(
+            !OrgRole::Moderator
+                .permissions()
+                .contains(Permissions::MANAGE_ROLES)
+        );
+        assert_eq!(OrgRole::Member.permissions(), Permissions::VIEW_MEMBERS);
+    }
+
+    #[test]
+    fn test_outranks() {
+        assert!(OrgRole::Owner.outranks(OrgRole::Admin));
+        assert!(OrgRole::Admin.outranks(OrgRole::Moderator));
+        assert!(!OrgRole::Admin.outranks(OrgRole::Admin));
+        assert!(!OrgRole::Member.outranks(OrgRole::Moderator));
+    }
+}
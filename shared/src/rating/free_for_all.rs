@@ -0,0 +1,153 @@
+use super::glicko2::{GameOutcome, Glicko2, Rating};
+
+/// Rate a free-for-all from its placements by decomposing it into pairwise games:
+/// every player beat everyone placed below them, lost to everyone placed above them
+/// and drew with everyone sharing their placement. All pairs are scored against the
+/// ratings going into the match, so the result doesn't depend on the players' order.
+///
+/// Returns the new ratings in the same order as `players`.
+pub fn rate_free_for_all(system: &Glicko2, players: &[(Rating, i32)]) -> Vec<Rating> {
+    players
+        .iter()
+        .enumerate()
+        .map(|(index, (rating, placement))| {
+            let games: Vec<GameOutcome> = players
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .map(|(_, (opponent, opponent_placement))| GameOutcome {
+                    opponent: *opponent,
+                    score: pairwise_score(*placement, *opponent_placement),
+                })
+                .collect();
+
+            system.update(*rating, &games)
+        })
+        .collect()
+}
+
+/// Score of the player placed `placement` against one placed `opponent_placement`;
+/// lower placements are better
+fn pairwise_score(placement: i32, opponent_placement: i32) -> f64 {
+    match placement.cmp(&opponent_placement) {
+        std::cmp::Ordering::Less => 1.0,
+        std::cmp::Ordering::Equal => 0.5,
+        std::cmp::Ordering::Greater => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rating::DEFAULT_VOLATILITY;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn rating(rating: f64, deviation: f64) -> Rating {
+        Rating {
+            rating,
+            deviation,
+            volatility: DEFAULT_VOLATILITY,
+        }
+    }
+
+    #[test]
+    fn test_matches_glickman_example() {
+        // Finishing third behind the 1700 and 1550 players and ahead of the 1400 one is
+        // the same rating period as Glickman's worked example
+        let players = [
+            (rating(1400.0, 30.0), 4),
+            (rating(1700.0, 300.0), 1),
+            (rating(1500.0, 200.0), 3),
+            (rating(1550.0, 100.0), 2),
+        ];
+
+        let rated = rate_free_for_all(&Glicko2::default(), &players);
+        assert!((rated[2].rating - 1464.06).abs() < 0.01);
+        assert!((rated[2].deviation - 151.52).abs() < 0.01);
+        assert!((rated[2].volatility - 0.05999).abs() < 0.00001);
+
+        // Everyone who beat the player gains, the player they beat loses
+        assert!(rated[1].rating > 1700.0);
+        assert!(rated[3].rating > 1550.0);
+        assert!(rated[0].rating < 1400.0);
+    }
+
+    #[test]
+    fn test_two_players_match_head_to_head() {
+        let system = Glicko2::default();
+        let winner = rating(1600.0, 80.0);
+        let loser = rating(1450.0, 150.0);
+
+        let rated = rate_free_for_all(&system, &[(winner, 1), (loser, 2)]);
+        assert_eq!(
+            rated[0],
+            system.update(
+                winner,
+                &[GameOutcome {
+                    opponent: loser,
+                    score: 1.0
+                }]
+            )
+        );
+        assert_eq!(
+            rated[1],
+            system.update(
+                loser,
+                &[GameOutcome {
+                    opponent: winner,
+                    score: 0.0
+                }]
+            )
+        );
+    }
+
+    #[test]
+    fn test_ties() {
+        let system = Glicko2::default();
+
+        // A full tie between equal players only makes their ratings more certain
+        let rated = rate_free_for_all(&system, &[(Rating::default(), 1); 3]);
+        for player in &rated {
+            assert!((player.rating - 1500.0).abs() < EPSILON);
+            assert!(player.deviation < 350.0);
+        }
+
+        // Tied players with equal ratings end up equal, between the winner and loser
+        let rated = rate_free_for_all(
+            &system,
+            &[
+                (Rating::default(), 2),
+                (Rating::default(), 1),
+                (Rating::default(), 2),
+                (Rating::default(), 4),
+            ],
+        );
+        assert_eq!(rated[0], rated[2]);
+        assert!(rated[1].rating > rated[0].rating);
+        assert!(rated[0].rating > rated[3].rating);
+        assert!(
+            (rated[0].rating + rated[1].rating + rated[2].rating + rated[3].rating - 4.0 * 1500.0)
+                .abs()
+                < EPSILON
+        );
+    }
+
+    #[test]
+    fn test_order_independent() {
+        let system = Glicko2::default();
+        let players = [
+            (rating(1520.0, 90.0), 2),
+            (rating(1480.0, 200.0), 1),
+            (rating(1610.0, 60.0), 3),
+            (rating(1390.0, 340.0), 3),
+        ];
+        let reversed: Vec<_> = players.iter().rev().copied().collect();
+
+        let rated = rate_free_for_all(&system, &players);
+        let mut rated_reversed = rate_free_for_all(&system, &reversed);
+        rated_reversed.reverse();
+
+        assert_eq!(rated, rated_reversed);
+    }
+}
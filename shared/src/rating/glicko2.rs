@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+pub const DEFAULT_RATING: f64 = 1500.0;
+pub const DEFAULT_DEVIATION: f64 = 350.0;
+pub const DEFAULT_VOLATILITY: f64 = 0.06;
+
+const SCALE: f64 = 173.7178; // Converts between the Glicko and Glicko-2 scales
+const CONVERGENCE_TOLERANCE: f64 = 0.000001;
+
+/// A player's Glicko-2 rating on the familiar Glicko scale (new players start at
+/// 1500 ± 350)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Rating {
+            rating: DEFAULT_RATING,
+            deviation: DEFAULT_DEVIATION,
+            volatility: DEFAULT_VOLATILITY,
+        }
+    }
+}
+
+impl Rating {
+    /// Pull a rating `strength` of the way back towards a new player's (0 keeps it,
+    /// 1 resets it completely), e.g. between seasons. The deviation grows by the same
+    /// share, since the old rating says less about the player's current skill.
+    pub fn soft_reset(self, strength: f64) -> Rating {
+        let strength = strength.clamp(0.0, 1.0);
+
+        Rating {
+            rating: self.rating + strength * (DEFAULT_RATING - self.rating),
+            deviation: self.deviation + strength * (DEFAULT_DEVIATION - self.deviation),
+            volatility: self.volatility,
+        }
+    }
+}
+
+/// One game of a rating period: the opponent's rating going into the period and the
+/// player's score (1 for a win, 0.5 for a draw, 0 for a loss)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameOutcome {
+    pub opponent: Rating,
+    pub score: f64,
+}
+
+/// Glicko-2 rating system parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glicko2 {
+    /// Constrains how fast volatility changes; sensible values are 0.3 to 1.2
+    pub tau: f64,
+}
+
+impl Default for Glicko2 {
+    fn default() -> Self {
+        Glicko2 { tau: 0.5 }
+    }
+}
+
+fn g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt()
+}
+
+fn expected_score(mu: f64, opponent_mu: f64, opponent_phi: f64) -> f64 {
+    1.0 / (1.0 + (-g(opponent_phi) * (mu - opponent_mu)).exp())
+}
+
+impl Glicko2 {
+    /// Rate a player after one rating period. A player with no games only grows more
+    /// uncertain, up to the deviation of a new player.
+    pub fn update(&self, player: Rating, games: &[GameOutcome]) -> Rating {
+        let mu = (player.rating - DEFAULT_RATING) / SCALE;
+        let phi = player.deviation / SCALE;
+        let sigma = player.volatility;
+
+        if games.is_empty() {
+            let phi_star = (phi * phi + sigma * sigma).sqrt();
+            return Rating {
+                deviation: (phi_star * SCALE).min(DEFAULT_DEVIATION),
+                ..player
+            };
+        }
+
+        // Estimated variance of the rating from the game outcomes alone, and the
+        // improvement those outcomes suggest
+        let mut inverse_variance = 0.0;
+        let mut score_sum = 0.0;
+        for game in games {
+            let opponent_mu = (game.opponent.rating - DEFAULT_RATING) / SCALE;
+            let opponent_phi = game.opponent.deviation / SCALE;
+            let g = g(opponent_phi);
+            let expected = expected_score(mu, opponent_mu, opponent_phi);
+
+            inverse_variance += g * g * expected * (1.0 - expected);
+            score_sum += g * (game.score - expected);
+        }
+        let variance = 1.0 / inverse_variance;
+        let delta = variance * score_sum;
+
+        let sigma = self.new_volatility(phi, sigma, variance, delta);
+
+        let phi_star = (phi * phi + sigma * sigma).sqrt();
+        let new_phi = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / variance).sqrt();
+        let new_mu = mu + new_phi * new_phi * score_sum;
+
+        Rating {
+            rating: new_mu * SCALE + DEFAULT_RATING,
+            deviation: new_phi * SCALE,
+            volatility: sigma,
+        }
+    }
+
+    /// Solve for the new volatility with the Illinois algorithm (step 5 of the paper)
+    fn new_volatility(&self, phi: f64, sigma: f64, variance: f64, delta: f64) -> f64 {
+        let a = (sigma * sigma).ln();
+        let tau_squared = self.tau * self.tau;
+        let f = |x: f64| {
+            let ex = x.exp();
+            let denominator = phi * phi + variance + ex;
+            ex * (delta * delta - phi * phi - variance - ex) / (2.0 * denominator * denominator)
+                - (x - a) / tau_squared
+        };
+
+        let mut upper = a;
+        let mut lower = if delta * delta > phi * phi + variance {
+            (delta * delta - phi * phi - variance).ln()
+        } else {
+            let mut k = 1.0;
+            while f(a - k * self.tau) < 0.0 {
+                k += 1.0;
+            }
+            a - k * self.tau
+        };
+
+        let mut f_upper = f(upper);
+        let mut f_lower = f(lower);
+        while (lower - upper).abs() > CONVERGENCE_TOLERANCE {
+            let next = upper + (upper - lower) * f_upper / (f_lower - f_upper);
+            let f_next = f(next);
+
+            if f_next * f_lower <= 0.0 {
+                upper = lower;
+                f_upper = f_lower;
+            } else {
+                f_upper /= 2.0;
+            }
+            lower = next;
+            f_lower = f_next;
+        }
+
+        (upper / 2.0).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rating(rating: f64, deviation: f64) -> Rating {
+        Rating {
+            rating,
+            deviation,
+            volatility: DEFAULT_VOLATILITY,
+        }
+    }
+
+    #[test]
+    fn test_glickman_example() {
+        // Worked example from Glickman's "Example of the Glicko-2 system"
+        let player = rating(1500.0, 200.0);
+        let games = [
+            GameOutcome {
+                opponent: rating(1400.0, 30.0),
+                score: 1.0,
+            },
+            GameOutcome {
+                opponent: rating(1550.0, 100.0),
+                score: 0.0,
+            },
+            GameOutcome {
+                opponent: rating(1700.0, 300.0),
+                score: 0.0,
+            },
+        ];
+
+        let rated = Glicko2::default().update(player, &games);
+        assert!((rated.rating - 1464.06).abs() < 0.01);
+        assert!((rated.deviation - 151.52).abs() < 0.01);
+        assert!((rated.volatility - 0.05999).abs() < 0.00001);
+    }
+
+    #[test]
+    fn test_soft_reset() {
+        let player = rating(1900.0, 50.0);
+
+        assert_eq!(player.soft_reset(0.0), player);
+        assert_eq!(player.soft_reset(1.0), Rating::default());
+
+        let halfway = player.soft_reset(0.5);
+        assert_eq!(halfway.rating, 1700.0);
+        assert_eq!(halfway.deviation, 200.0);
+        assert_eq!(halfway.volatility, DEFAULT_VOLATILITY);
+
+        let below_mean = rating(1300.0, 100.0).soft_reset(0.25);
+        assert_eq!(below_mean.rating, 1350.0);
+    }
+
+    #[test]
+    fn test_inactive_player_grows_uncertain() {
+        let player = rating(1500.0, 200.0);
+
+        let rated = Glicko2::default().update(player, &[]);
+        assert_eq!(rated.rating, 1500.0);
+        assert!((rated.deviation - 200.2714).abs() < 0.0001);
+
+        let new_player = Glicko2::default().update(Rating::default(), &[]);
+        assert_eq!(new_player.deviation, DEFAULT_DEVIATION);
+    }
+}
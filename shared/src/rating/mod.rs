@@ -0,0 +1,44 @@
+//! Player skill ratings computed from match results
+//!
+//! Ratings use [Glicko-2](http://www.glicko.net/glicko/glicko2.pdf): every player has
+//! a rating, a rating deviation (how uncertain the rating is) and a volatility (how
+//! erratic their results are). Each match is rated as its own rating period, and
+//! matches with more than two players are decomposed into pairwise games by placement.
+
+mod free_for_all;
+mod glicko2;
+
+pub use free_for_all::*;
+pub use glicko2::*;
+
+/// New ratings for a match's participants from their placements (lower is better,
+/// ties share a placement), in the same order as `players`. Returns `None` when there
+/// is nobody to compare against.
+pub fn rate_match(system: &Glicko2, players: &[(Rating, i32)]) -> Option<Vec<Rating>> {
+    if players.len() < 2 {
+        return None;
+    }
+
+    Some(rate_free_for_all(system, players))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_match() {
+        let system = Glicko2::default();
+        let players = [(Rating::default(), 2), (Rating::default(), 1)];
+
+        let rated = rate_match(&system, &players).unwrap();
+        assert!(rated[0].rating < 1500.0);
+        assert!(rated[1].rating > 1500.0);
+        assert!((rated[0].rating + rated[1].rating - 3000.0).abs() < 1e-9);
+
+        let drawn = rate_match(&system, &[(Rating::default(), 1), (Rating::default(), 1)]).unwrap();
+        assert!((drawn[0].rating - 1500.0).abs() < 1e-9);
+
+        assert!(rate_match(&system, &[(Rating::default(), 1)]).is_none());
+    }
+}